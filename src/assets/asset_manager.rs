@@ -0,0 +1,182 @@
+extern crate sdl3;
+
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    path::{Path, PathBuf}
+};
+
+use sdl3::{
+    pixels::{Color, PixelFormat},
+    rect::Rect,
+    render::{Texture, TextureCreator},
+    surface::Surface,
+    video::WindowContext
+};
+
+use crate::assets::image_loader::{self, ImageLoadError};
+
+/// Side of the checkerboard texture drawn in place of images that failed to load.
+const MISSING_TEXTURE_SIZE: u32 = 16;
+
+/// Lightweight reference to an asset owned by the `AssetManager`.
+///
+/// Handles are `Copy` and carry no lifetime, so game objects can store them
+/// freely; `T` only keeps handles to different kinds of asset apart.
+pub struct Handle<T> {
+    index: usize,
+    kind: PhantomData<fn() -> T>
+}
+
+// Implemented by hand so handles are `Copy`/`Eq`/`Hash` whatever `T` is
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> Hash for Handle<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Handle({})", self.index)
+    }
+}
+
+impl<T> Handle<T> {
+    fn new(index: usize) -> Self {
+        Self { index, kind: PhantomData }
+    }
+}
+
+/// Marker for texture handles.
+pub enum TextureAsset {}
+
+pub type TextureHandle = Handle<TextureAsset>;
+
+/// Loads assets once and hands out handles to them.
+///
+/// Loading the same path twice returns the same handle. An asset that fails
+/// to load is replaced by a placeholder (a magenta checkerboard for
+/// textures) so the game keeps running, and the failure is kept for
+/// `failures` to report. Reloading an asset swaps it in place, so every
+/// holder of its handle sees the new version.
+pub struct AssetManager<'a> {
+    texture_creator: &'a TextureCreator<WindowContext>,
+    textures: Vec<Option<Texture<'a>>>,                 // `None` while the image failed to load
+    texture_paths: HashMap<PathBuf, TextureHandle>,     // Cache, by canonical path
+    missing_texture: Texture<'a>,
+    failures: Vec<ImageLoadError>
+}
+
+impl<'a> AssetManager<'a> {
+    /// Creates an empty asset manager that creates textures with `texture_creator`.
+    pub fn new(texture_creator: &'a TextureCreator<WindowContext>) -> Result<Self, String> {
+        Ok(Self {
+            texture_creator,
+            textures: Vec::new(),
+            texture_paths: HashMap::new(),
+            missing_texture: Self::create_missing_texture(texture_creator)?,
+            failures: Vec::new()
+        })
+    }
+
+    /// Magenta and black checkerboard, hard to miss on screen.
+    fn create_missing_texture(texture_creator: &'a TextureCreator<WindowContext>) -> Result<Texture<'a>, String> {
+        let half = MISSING_TEXTURE_SIZE / 2;
+        let mut surface = Surface::new(MISSING_TEXTURE_SIZE, MISSING_TEXTURE_SIZE, PixelFormat::RGBA8888)
+            .map_err(|e| e.to_string())?;
+        surface.fill_rect(None, Color::RGB(255, 0, 255)).map_err(|e| e.to_string())?;
+        surface.fill_rects(
+            &[Rect::new(0, 0, half, half), Rect::new(half as i32, half as i32, half, half)],
+            Color::RGB(0, 0, 0)
+        ).map_err(|e| e.to_string())?;
+        surface.as_texture(texture_creator).map_err(|e| e.to_string())
+    }
+
+    /// Loads the image at `path`, or returns the cached handle if it was
+    /// loaded before.
+    ///
+    /// # Returns
+    /// The texture's handle, drawn as the placeholder if it failed to load
+    pub fn load_texture(&mut self, path: impl AsRef<Path>) -> TextureHandle {
+        let path = path.as_ref();
+        let key = Self::cache_key(path);
+        if let Some(handle) = self.texture_paths.get(&key) {
+            return *handle;
+        }
+
+        // A failed image still gets its own slot, so a later reload can fill
+        // it in without affecting other assets
+        let texture = match image_loader::load_texture(path, self.texture_creator) {
+            Ok(texture) => Some(texture),
+            Err(error) => {
+                self.failures.push(error);
+                None
+            }
+        };
+        self.textures.push(texture);
+        let handle = Handle::new(self.textures.len() - 1);
+        self.texture_paths.insert(key, handle);
+        handle
+    }
+
+    /// Loads the image at `path` again if it was loaded before, replacing
+    /// the old texture behind the same handle.
+    ///
+    /// # Returns
+    /// `Ok(false)` if no texture was loaded from `path`. On error the old
+    /// texture is kept.
+    pub fn reload_texture(&mut self, path: impl AsRef<Path>) -> Result<bool, ImageLoadError> {
+        let path = path.as_ref();
+        let Some(handle) = self.texture_paths.get(&Self::cache_key(path)).copied() else {
+            return Ok(false);
+        };
+
+        self.textures[handle.index] = Some(image_loader::load_texture(path, self.texture_creator)?);
+        Ok(true)
+    }
+
+    /// Paths are compared in canonical form, so `res/a.png` and
+    /// `res/./a.png` share one cache entry.
+    fn cache_key(path: &Path) -> PathBuf {
+        fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+    }
+
+    /// The texture behind `handle`, or the placeholder if it failed to load.
+    pub fn texture(&self, handle: TextureHandle) -> &Texture<'a> {
+        self.textures[handle.index].as_ref().unwrap_or(&self.missing_texture)
+    }
+
+    /// The texture behind `handle`, mutably, e.g. to tint it. The shared
+    /// placeholder is returned for images that failed to load.
+    pub fn texture_mut(&mut self, handle: TextureHandle) -> &mut Texture<'a> {
+        self.textures[handle.index].as_mut().unwrap_or(&mut self.missing_texture)
+    }
+
+    /// Whether `handle` is the placeholder for an image that failed to load.
+    pub fn is_missing(&self, handle: TextureHandle) -> bool {
+        self.textures[handle.index].is_none()
+    }
+
+    /// Every asset that failed to load so far.
+    pub fn failures(&self) -> &[ImageLoadError] {
+        &self.failures
+    }
+}
@@ -0,0 +1,76 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime}
+};
+
+/// How often the watched directory is rescanned.
+const SCAN_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Watches a directory tree for files that are added or modified, by
+/// comparing modification times on a fixed interval.
+///
+/// Meant for development: an artist saves a tile or map and the running
+/// game picks it up within half a second.
+pub struct ResourceWatcher {
+    root: PathBuf,
+    modified: HashMap<PathBuf, SystemTime>,     // Last seen modification time of each file
+    last_scan: Instant
+}
+
+impl ResourceWatcher {
+    /// Starts watching `root`. Files that already exist are not reported.
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        let mut watcher = Self {
+            root: root.as_ref().to_path_buf(),
+            modified: HashMap::new(),
+            last_scan: Instant::now()
+        };
+        watcher.modified = watcher.scan();
+        watcher
+    }
+
+    /// Files added or modified since the previous call, in path order.
+    /// Returns nothing until the scan interval has passed.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        if self.last_scan.elapsed() < SCAN_INTERVAL {
+            return Vec::new();
+        }
+        self.last_scan = Instant::now();
+
+        let current = self.scan();
+        let mut changed: Vec<PathBuf> = current.iter()
+            .filter(|(path, time)| self.modified.get(*path) != Some(*time))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.sort();
+        self.modified = current;
+        changed
+    }
+
+    /// Modification time of every file under the root.
+    fn scan(&self) -> HashMap<PathBuf, SystemTime> {
+        let mut files = HashMap::new();
+        let mut directories = vec![self.root.clone()];
+
+        // Files that vanish or cannot be read mid-scan are simply skipped
+        while let Some(directory) = directories.pop() {
+            let Ok(entries) = fs::read_dir(&directory) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let Ok(metadata) = entry.metadata() else {
+                    continue;
+                };
+                if metadata.is_dir() {
+                    directories.push(path);
+                } else if let Ok(time) = metadata.modified() {
+                    files.insert(path, time);
+                }
+            }
+        }
+        files
+    }
+}
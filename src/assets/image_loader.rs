@@ -0,0 +1,98 @@
+extern crate sdl3;
+
+use std::{
+    fmt,
+    path::{Path, PathBuf}
+};
+
+use sdl3::{
+    image::ImageIOStream,
+    iostream::IOStream,
+    render::{Texture, TextureCreator},
+    video::WindowContext
+};
+
+/// File extensions that can be loaded as images, with the SDL_image decoder
+/// used for each.
+const IMAGE_TYPES: [(&str, &str); 8] = [
+    ("png", "PNG"),
+    ("bmp", "BMP"),
+    ("jpg", "JPG"),
+    ("jpeg", "JPG"),
+    ("gif", "GIF"),
+    ("tga", "TGA"),
+    ("webp", "WEBP"),
+    ("qoi", "QOI")
+];
+
+/// Everything that can go wrong while loading an image.
+#[derive(Debug)]
+pub enum ImageLoadError {
+    UnsupportedFormat { path: PathBuf },
+    Io { path: PathBuf, message: String },
+    Decode { path: PathBuf, message: String },
+    Texture { path: PathBuf, message: String }
+}
+
+impl fmt::Display for ImageLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageLoadError::UnsupportedFormat { path } => {
+                let supported: Vec<&str> = IMAGE_TYPES.iter().map(|(extension, _)| *extension).collect();
+                write!(f, "{}: not a supported image file (expected one of: {})", path.display(), supported.join(", "))
+            },
+            ImageLoadError::Io { path, message } => {
+                write!(f, "{}: failed to open image: {}", path.display(), message)
+            },
+            ImageLoadError::Decode { path, message } => {
+                write!(f, "{}: failed to decode image: {}", path.display(), message)
+            },
+            ImageLoadError::Texture { path, message } => {
+                write!(f, "{}: failed to create texture: {}", path.display(), message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImageLoadError {}
+
+/// SDL_image decoder for `path`, picked by its extension (case-insensitive).
+///
+/// # Returns
+/// `None` for files that are not images, such as a stray `.DS_Store`
+pub fn image_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    IMAGE_TYPES.iter()
+        .find(|(known, _)| *known == extension)
+        .map(|(_, decoder)| *decoder)
+}
+
+/// Whether `path` has an image extension `load_texture` accepts.
+pub fn is_image(path: &Path) -> bool {
+    image_type(path).is_some()
+}
+
+/// Loads the image at `path` into a GPU texture, decoding it according to
+/// its extension. Alpha channels (e.g. in PNGs) are kept.
+pub fn load_texture<'a>(
+    path: impl AsRef<Path>,
+    texture_creator: &'a TextureCreator<WindowContext>
+) -> Result<Texture<'a>, ImageLoadError> {
+    let path = path.as_ref();
+    let decoder = image_type(path).ok_or_else(|| ImageLoadError::UnsupportedFormat {
+        path: path.to_path_buf()
+    })?;
+
+    let stream = IOStream::from_file(path, "rb").map_err(|e| ImageLoadError::Io {
+        path: path.to_path_buf(),
+        message: e.to_string()
+    })?;
+    let surface = stream.load_typed(decoder).map_err(|e| ImageLoadError::Decode {
+        path: path.to_path_buf(),
+        message: e.to_string()
+    })?;
+    surface.as_texture(texture_creator).map_err(|e| ImageLoadError::Texture {
+        path: path.to_path_buf(),
+        message: e.to_string()
+    })
+}
@@ -0,0 +1,3 @@
+pub mod asset_manager;
+pub mod hot_reload;
+pub mod image_loader;
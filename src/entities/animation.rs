@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// What a clip does when it reaches its last frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaybackMode {
+    Loop,       // Start over from the first frame
+    PingPong,   // Play backwards to the first frame, then forwards again
+    Once        // Stay on the last frame
+}
+
+/// A sequence of sprite sheet frames with how long each is shown.
+pub struct AnimationClip {
+    pub frames: Vec<usize>,         // Frame indices into the sprite sheet
+    pub durations: Vec<Duration>,   // Display time of each frame, same length as `frames`
+    pub mode: PlaybackMode
+}
+
+/// Plays the clips of a sprite sheet for one entity.
+///
+/// The entity picks a named state (`idle`, `walk`, ...) and the clip for
+/// its facing direction is looked up in the sheet each tick, so turning
+/// keeps the animation's timing while changing state restarts it.
+pub struct AnimationPlayer {
+    state: String,
+    frame: usize,           // Position within the clip's frames
+    elapsed: Duration,      // Time spent on the current frame
+    forward: bool,          // Play direction, for ping-pong clips
+    finished: bool          // Whether a `Once` clip has reached its end
+}
+
+impl AnimationPlayer {
+    pub fn new(state: &str) -> Self {
+        Self {
+            state: state.to_string(),
+            frame: 0,
+            elapsed: Duration::ZERO,
+            forward: true,
+            finished: false
+        }
+    }
+
+    /// Name of the state being played.
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    /// Switches to `state`, restarting from its first frame. Does nothing
+    /// if that state is already playing.
+    pub fn set_state(&mut self, state: &str) {
+        if self.state != state {
+            *self = Self::new(state);
+        }
+    }
+
+    /// Whether a `Once` clip has shown its last frame for its full duration.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Advances the animation by `delta` through `clip`.
+    pub fn update(&mut self, clip: &AnimationClip, delta: Duration) {
+        if self.finished || clip.frames.is_empty() {
+            return;
+        }
+        self.frame = self.frame.min(clip.frames.len() - 1);
+        self.elapsed += delta;
+
+        // A long delta may skip over several short frames
+        while self.elapsed >= clip.durations[self.frame] {
+            self.elapsed -= clip.durations[self.frame];
+            if !self.advance(clip) {
+                self.elapsed = Duration::ZERO;
+                break;
+            }
+        }
+    }
+
+    /// Moves to the next frame according to the clip's mode.
+    ///
+    /// # Returns
+    /// `false` if the animation cannot move on (a single frame, or the end
+    /// of a `Once` clip)
+    fn advance(&mut self, clip: &AnimationClip) -> bool {
+        let last = clip.frames.len() - 1;
+        if clip.mode == PlaybackMode::Once && self.frame == last {
+            self.finished = true;
+            return false;
+        }
+        if last == 0 {
+            return false;
+        }
+
+        match clip.mode {
+            PlaybackMode::Loop => self.frame = (self.frame + 1) % clip.frames.len(),
+            PlaybackMode::Once => self.frame += 1,
+            PlaybackMode::PingPong => {
+                if (self.forward && self.frame == last) || (!self.forward && self.frame == 0) {
+                    self.forward = !self.forward;
+                }
+                self.frame = if self.forward { self.frame + 1 } else { self.frame - 1 };
+            }
+        }
+        true
+    }
+
+    /// Sprite sheet frame to draw for `clip`.
+    pub fn current_frame(&self, clip: &AnimationClip) -> Option<usize> {
+        clip.frames.get(self.frame.min(clip.frames.len().saturating_sub(1))).copied()
+    }
+}
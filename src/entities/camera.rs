@@ -1,69 +1,192 @@
 extern crate sdl3;
 
 use sdl3::{
-    pixels::Color, rect::Rect, render::Canvas, video::Window
+    rect::Rect, render::Canvas, video::Window
 };
 
 use crate::{
-    events::key_handler::{Direction, Keys}, 
-    tiles::tile_handler::{self, Map, TileHandler}
+    assets::asset_manager::AssetManager,
+    tiles::tile_handler::{LayerKind, Map, MapLayer, TileHandler}
 };
 
+/// The view onto the world.
+///
+/// The camera follows a target (normally the player) and never scrolls past
+/// the edges of the map. `camera_x`/`camera_y` are the world coordinates of
+/// the top-left corner of the screen.
+#[derive(Clone)]
 pub struct Camera{
-    speed: i32,
     pub camera_x: i32,
     pub camera_y: i32,
+    previous_x: i32,                    // Position before the last update, for render interpolation
+    previous_y: i32,
     camera_screen_width: u32,
-    camera_screen_height: u32
+    camera_screen_height: u32,
+    pub deadzone: Option<(u32, u32)>,   // Box around the screen centre the target can move in without scrolling
+    pub smoothing: f32                  // Fraction of the remaining distance covered per update; 1.0 snaps
 }
 
 impl Camera {
-    pub fn new(x: i32, y: i32, camera_width: u32, camera_height: u32, speed: i32) -> Self{
+    pub fn new(x: i32, y: i32, camera_width: u32, camera_height: u32) -> Self{
         Self { 
-            speed: speed,
             camera_x: x,
             camera_y: y,
+            previous_x: x,
+            previous_y: y,
             camera_screen_width: camera_width,
             camera_screen_height: camera_height,          
+            deadzone: None,
+            smoothing: 1.0
         }
     }
 
-    pub fn update(&mut self, keys: &mut Keys, on_collision: bool) {
-        if !on_collision{
-            if keys.w {
-                self.camera_y += self.speed;
-            }
-            if keys.a {
-                self.camera_x += self.speed;
-            }
-            if keys.s {
-                self.camera_y -= self.speed;
-            }
-            if keys.d {
-                self.camera_x -= self.speed;
-            }
+    /// Moves the camera towards `target`, a world-space rectangle.
+    ///
+    /// With a deadzone the camera only scrolls once the target's centre
+    /// leaves the deadzone box; with smoothing below 1.0 it eases towards
+    /// the new position over several updates.
+    ///
+    /// # Arguments
+    /// * `target` - World position and size of the followed entity
+    /// * `map_width`, `map_height` - Size of the current map in pixels
+    pub fn update(&mut self, target: Rect, map_width: u32, map_height: u32) {
+        let (desired_x, desired_y) = self.desired_position(target);
+        (self.previous_x, self.previous_y) = (self.camera_x, self.camera_y);
+
+        self.camera_x += Self::smoothed_step(desired_x - self.camera_x, self.smoothing);
+        self.camera_y += Self::smoothed_step(desired_y - self.camera_y, self.smoothing);
+        self.clamp_to_map(map_width, map_height);
+    }
+
+    /// Centres the camera on `target` immediately, ignoring deadzone and
+    /// smoothing. Used after teleports such as warps.
+    pub fn snap_to(&mut self, target: Rect, map_width: u32, map_height: u32) {
+        let (center_x, center_y) = (target.center().x(), target.center().y());
+        self.camera_x = center_x - self.camera_screen_width as i32 / 2;
+        self.camera_y = center_y - self.camera_screen_height as i32 / 2;
+        self.clamp_to_map(map_width, map_height);
+        (self.previous_x, self.previous_y) = (self.camera_x, self.camera_y);
+    }
+
+    /// A copy of the camera placed between its previous and current
+    /// position, `alpha` being how far into the next tick rendering happens.
+    pub fn interpolated(&self, alpha: f32) -> Camera {
+        let lerp = |from: i32, to: i32| from + ((to - from) as f32 * alpha).round() as i32;
+        Camera {
+            camera_x: lerp(self.previous_x, self.camera_x),
+            camera_y: lerp(self.previous_y, self.camera_y),
+            ..self.clone()
         }
-        
     }
 
+    /// Where the camera wants to be for the target's current position.
+    fn desired_position(&self, target: Rect) -> (i32, i32) {
+        let (center_x, center_y) = (target.center().x(), target.center().y());
+        let (screen_width, screen_height) = (self.camera_screen_width as i32, self.camera_screen_height as i32);
+
+        let Some((deadzone_width, deadzone_height)) = self.deadzone else {
+            return (center_x - screen_width / 2, center_y - screen_height / 2);
+        };
+
+        // Distance from the screen edge to the deadzone box on each side
+        let margin_x = (screen_width - deadzone_width as i32) / 2;
+        let margin_y = (screen_height - deadzone_height as i32) / 2;
+
+        let desired_x = if center_x < self.camera_x + margin_x {
+            center_x - margin_x
+        } else if center_x > self.camera_x + margin_x + deadzone_width as i32 {
+            center_x - margin_x - deadzone_width as i32
+        } else {
+            self.camera_x
+        };
+        let desired_y = if center_y < self.camera_y + margin_y {
+            center_y - margin_y
+        } else if center_y > self.camera_y + margin_y + deadzone_height as i32 {
+            center_y - margin_y - deadzone_height as i32
+        } else {
+            self.camera_y
+        };
+
+        (desired_x, desired_y)
+    }
+
+    /// Covers `smoothing` of `distance`, but always at least one pixel so
+    /// the camera never stalls just short of its target.
+    fn smoothed_step(distance: i32, smoothing: f32) -> i32 {
+        if distance == 0 {
+            return 0;
+        }
+        let step = (distance as f32 * smoothing.clamp(0.0, 1.0)).round() as i32;
+        if step == 0 { distance.signum() } else { step }
+    }
+
+    /// Keeps the view inside the map. Maps smaller than the screen are centred.
+    fn clamp_to_map(&mut self, map_width: u32, map_height: u32) {
+        self.camera_x = Self::clamp_axis(self.camera_x, self.camera_screen_width, map_width);
+        self.camera_y = Self::clamp_axis(self.camera_y, self.camera_screen_height, map_height);
+    }
+
+    fn clamp_axis(position: i32, screen_size: u32, map_size: u32) -> i32 {
+        if map_size <= screen_size {
+            -((screen_size - map_size) as i32 / 2)
+        } else {
+            position.clamp(0, (map_size - screen_size) as i32)
+        }
+    }
+
+    /// Converts a world-space rectangle to screen space.
+    pub fn to_screen(&self, rect: Rect) -> Rect {
+        Rect::new(rect.x - self.camera_x, rect.y - self.camera_y, rect.width(), rect.height())
+    }
+
+    /// Draws the ground and decoration layers of `map` that are in view.
+    /// Called before the player is rendered.
     pub fn draw_camera(
         &self, 
-        max_world_row: u32,
-        max_world_col: u32,
         max_screen_row: u32,
         max_screen_col: u32,
         tile_handler: &TileHandler,
+        assets: &AssetManager,
+        canvas: &mut Canvas<Window>, 
+        map: &Map){
+        for layer in map.layers.iter().filter(|layer| layer.kind != LayerKind::Overlay) {
+            self.draw_layer(max_screen_row, max_screen_col, tile_handler, assets, canvas, layer);
+        }
+    }
+
+    /// Draws the overlay layers of `map` (roofs, tree canopies) that are in
+    /// view. Called after the player is rendered so they cover it.
+    pub fn draw_overlay(
+        &self, 
+        max_screen_row: u32,
+        max_screen_col: u32,
+        tile_handler: &TileHandler,
+        assets: &AssetManager,
         canvas: &mut Canvas<Window>, 
         map: &Map){
+        for layer in map.layers.iter().filter(|layer| layer.kind == LayerKind::Overlay) {
+            self.draw_layer(max_screen_row, max_screen_col, tile_handler, assets, canvas, layer);
+        }
+    }
 
-        let start_world_tile_x = self.camera_x.unsigned_abs()/ tile_handler.tile_size ;
-        let start_world_tile_y = self.camera_y.unsigned_abs()/ tile_handler.tile_size;
+    fn draw_layer(
+        &self, 
+        max_screen_row: u32,
+        max_screen_col: u32,
+        tile_handler: &TileHandler,
+        assets: &AssetManager,
+        canvas: &mut Canvas<Window>, 
+        layer: &MapLayer){
+        let max_world_row = layer.map.len() as u32;
+        let max_world_col = layer.map.first().map_or(0, |row| row.len() as u32);
+
+        // The camera is negative when a map smaller than the screen is centred
+        let start_world_tile_x = self.camera_x.max(0) as u32 / tile_handler.tile_size;
+        let start_world_tile_y = self.camera_y.max(0) as u32 / tile_handler.tile_size;
         let mut world_tile_x = start_world_tile_x;
         let mut world_tile_y = start_world_tile_y;
-        let mut x = self.camera_x;
-        let mut y= self.camera_y;
-       
-        println!("X: {}, Y:{}", self.camera_x, self.camera_y);
+        let mut x = -self.camera_x;
+        let mut y= -self.camera_y;
 
         for row in 0 .. max_world_row{
             for col in 0  .. max_world_col{
@@ -72,22 +195,20 @@ impl Camera {
                    col >= start_world_tile_x &&
                    col < start_world_tile_x + max_screen_col + 1
                 {
-                    let image = &tile_handler.tiles[map
-                        .map[row as usize][col as usize]
-                        .index as usize]
-                        .image;
-           
-                    // Define source rectangle (full texture)
-                    let src_rect = Rect::new(0, 0, tile_handler.tile_size as u32, tile_handler.tile_size as u32);
-
-                    let dest_rect = Rect::new(x, y, tile_handler.tile_size as u32, tile_handler.tile_size as u32);
-
-                    // Copy the texture to the canvas at the player's position
-                    canvas.copy(image, src_rect, dest_rect).ok().unwrap();
+                    // Empty cells let the layers below show through, and tiles
+                    // without a texture (e.g. never loaded) are skipped
+                    let texture = layer.map[row as usize][col as usize].as_ref()
+                        .and_then(|pixel| tile_handler.tiles[pixel.index as usize].texture);
+                    if let Some(texture) = texture {
+                        let dest_rect = Rect::new(x, y, tile_handler.tile_size as u32, tile_handler.tile_size as u32);
+
+                        // Copy the whole texture to the tile's place on screen
+                        canvas.copy(assets.texture(texture), None, dest_rect).ok().unwrap();
+                    }
                 }
                 x += tile_handler.tile_size as i32;
             }
-            x = self.camera_x;
+            x = -self.camera_x;
             y += tile_handler.tile_size as i32;
 
         }  
@@ -0,0 +1,115 @@
+extern crate sdl3;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration
+};
+
+use sdl3::{
+    rect::Rect,
+    render::Canvas,
+    video::Window
+};
+
+use crate::{
+    assets::asset_manager::AssetManager,
+    entities::{
+        animation::AnimationPlayer,
+        camera::Camera,
+        sprite_sheet::{SpriteSheet, SpriteSheetError}
+    },
+    events::key_handler::Direction
+};
+
+// Animation states every character sprite sheet must define
+const IDLE_STATE: &str = "idle";
+const WALK_STATE: &str = "walk";
+
+/// Draws a walking character (the player or an NPC) from its sprite sheet,
+/// playing the sheet's `walk` state while the character moves and `idle`
+/// otherwise, in the character's facing direction.
+pub struct CharacterSprite {
+    path: PathBuf,              // Sheet metadata file, for hot reloading
+    sheet: SpriteSheet,         // One texture holding every frame, with clip timing
+    animation: AnimationPlayer
+}
+
+impl CharacterSprite {
+    /// Loads the sprite sheet at `path` and checks it has the states
+    /// characters use.
+    ///
+    /// # Arguments
+    /// * `path` - Sprite sheet metadata file
+    /// * `assets` - Loads the sprite sheet texture
+    pub fn load(path: impl AsRef<Path>, assets: &mut AssetManager) -> Result<Self, SpriteSheetError> {
+        let path = path.as_ref().to_path_buf();
+        let sheet = Self::load_sheet(&path, assets)?;
+        Ok(Self { path, sheet, animation: AnimationPlayer::new(IDLE_STATE) })
+    }
+
+    fn load_sheet(path: &Path, assets: &mut AssetManager) -> Result<SpriteSheet, SpriteSheetError> {
+        let sheet = SpriteSheet::load(path, assets)?;
+        for state in [IDLE_STATE, WALK_STATE] {
+            if !sheet.has_state(state) {
+                return Err(SpriteSheetError::MissingState {
+                    path: path.to_path_buf(),
+                    state: state.to_string()
+                });
+            }
+        }
+        Ok(sheet)
+    }
+
+    /// Reloads the sprite sheet's frames and clips if `changed` is its
+    /// metadata file. The animation carries on from where it was; its
+    /// texture is reloaded separately by `AssetManager::reload_texture`.
+    ///
+    /// # Returns
+    /// `Ok(false)` if `changed` is some other file. On error the old sheet
+    /// is kept.
+    pub fn reload(&mut self, changed: &Path, assets: &mut AssetManager) -> Result<bool, SpriteSheetError> {
+        let same_file = match (fs::canonicalize(changed), fs::canonicalize(&self.path)) {
+            (Ok(changed), Ok(sheet)) => changed == sheet,
+            _ => false
+        };
+        if !same_file {
+            return Ok(false);
+        }
+        self.sheet = Self::load_sheet(&self.path, assets)?;
+        Ok(true)
+    }
+
+    /// Advances the animation by one tick of length `delta`.
+    ///
+    /// # Arguments
+    /// * `moving` - Whether the character tried to move this tick, even against a wall
+    /// * `direction` - Direction the character faces
+    /// * `delta` - Game time covered by the tick
+    pub fn update(&mut self, moving: bool, direction: Direction, delta: Duration) {
+        self.animation.set_state(if moving { WALK_STATE } else { IDLE_STATE });
+
+        if let Some(clip) = self.sheet.state_clip(self.animation.state(), direction) {
+            self.animation.update(clip, delta);
+        }
+    }
+
+    /// Renders the character, using the current frame of the clip for its
+    /// direction.
+    ///
+    /// # Arguments
+    /// * `canvas` - SDL canvas to draw on
+    /// * `assets` - Holds the sprite sheet texture
+    /// * `direction` - Direction the character faces
+    /// * `rect` - World rectangle to draw at, normally the interpolated one
+    /// * `camera` - Converts the world position to the screen
+    pub fn render(&self, canvas: &mut Canvas<Window>, assets: &AssetManager, direction: Direction, rect: Rect, camera: &Camera) {
+        let frame = self.sheet.state_clip(self.animation.state(), direction)
+            .and_then(|clip| self.animation.current_frame(clip));
+
+        // Copy the frame from the sheet to the canvas at the character's position
+        if let Some(frame) = frame {
+            self.sheet.draw(canvas, assets, frame, camera.to_screen(rect));
+        }
+    }
+}
@@ -0,0 +1,150 @@
+extern crate sdl3;
+
+use std::{path::Path, time::Duration};
+
+use sdl3::{
+    rect::Rect,
+    render::Canvas,
+    video::Window
+};
+
+use crate::{
+    assets::asset_manager::AssetManager,
+    entities::{
+        camera::Camera,
+        character_sprite::CharacterSprite,
+        npc::Npc,
+        player::{Player, PLAYER_SPRITE_SHEET}
+    },
+    events::{collision_handler::HitBox, key_handler::Direction},
+    world::World
+};
+
+/// Identifies an entity in the `World`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntityId {
+    Player,
+    Npc(usize)      // Index into `World::npcs`
+}
+
+/// What the world needs to know about anything that stands on a map to
+/// move it, collide with it and draw it in the right order.
+pub trait Entity {
+    /// Box that collides with tiles and other entities.
+    fn hit_box(&self) -> HitBox;
+
+    /// Position between the previous and current tick, for rendering.
+    fn interpolated_rect(&self, alpha: f32) -> Rect;
+
+    fn direction(&self) -> Direction;
+
+    /// Whether the entity tried to move during the last update.
+    fn is_moving(&self) -> bool;
+
+    /// World Y of the entity's feet (the bottom of its hitbox) at `alpha`.
+    /// Entities are drawn from the smallest foot Y to the largest, so those
+    /// standing further down the screen overlap those behind them.
+    fn foot_y(&self, alpha: f32) -> i32 {
+        let hit_box = HitBox::for_sprite(self.interpolated_rect(alpha));
+        hit_box.y + hit_box.height as i32
+    }
+}
+
+impl Entity for Player {
+    fn hit_box(&self) -> HitBox {
+        Player::hit_box(self)
+    }
+
+    fn interpolated_rect(&self, alpha: f32) -> Rect {
+        Player::interpolated_rect(self, alpha)
+    }
+
+    fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    fn is_moving(&self) -> bool {
+        Player::is_moving(self)
+    }
+}
+
+impl Entity for Npc {
+    fn hit_box(&self) -> HitBox {
+        Npc::hit_box(self)
+    }
+
+    fn interpolated_rect(&self, alpha: f32) -> Rect {
+        Npc::interpolated_rect(self, alpha)
+    }
+
+    fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    fn is_moving(&self) -> bool {
+        Npc::is_moving(self)
+    }
+}
+
+/// The sprite of every entity in a `World`, animated and drawn together.
+pub struct EntitySprites {
+    player: CharacterSprite,
+    npcs: Vec<CharacterSprite>      // In the same order as `World::npcs`
+}
+
+impl EntitySprites {
+    /// Loads the player's sprite sheet and that of every NPC in `world`.
+    pub fn load(world: &World, assets: &mut AssetManager) -> Result<Self, String> {
+        let load = |path: &Path, assets: &mut AssetManager| {
+            CharacterSprite::load(path, assets).map_err(|e| e.to_string())
+        };
+        let player = load(Path::new(PLAYER_SPRITE_SHEET), assets)?;
+        let npcs = world.npcs.iter()
+            .map(|npc| load(&npc.sprite, assets))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { player, npcs })
+    }
+
+    fn sprite(&self, id: EntityId) -> &CharacterSprite {
+        match id {
+            EntityId::Player => &self.player,
+            EntityId::Npc(index) => &self.npcs[index]
+        }
+    }
+
+    fn sprite_mut(&mut self, id: EntityId) -> &mut CharacterSprite {
+        match id {
+            EntityId::Player => &mut self.player,
+            EntityId::Npc(index) => &mut self.npcs[index]
+        }
+    }
+
+    /// Every sprite, for hot reloading.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut CharacterSprite> {
+        std::iter::once(&mut self.player).chain(&mut self.npcs)
+    }
+
+    /// Advances the animation of every entity on the active map by one tick
+    /// of length `delta`.
+    pub fn update(&mut self, world: &World, delta: Duration) {
+        for id in world.entity_ids() {
+            let entity = world.entity(id);
+            self.sprite_mut(id).update(entity.is_moving(), entity.direction(), delta);
+        }
+    }
+
+    /// Draws every entity on the active map, back to front by foot Y.
+    ///
+    /// # Arguments
+    /// * `canvas` - SDL canvas to draw on
+    /// * `assets` - Holds the sprite sheet textures
+    /// * `world` - Entities to draw
+    /// * `alpha` - How far into the next tick rendering happens
+    /// * `camera` - Converts world positions to the screen
+    pub fn render(&self, canvas: &mut Canvas<Window>, assets: &AssetManager, world: &World, alpha: f32, camera: &Camera) {
+        for id in world.draw_order(alpha) {
+            let entity = world.entity(id);
+            self.sprite(id).render(canvas, assets, entity.direction(), entity.interpolated_rect(alpha), camera);
+        }
+    }
+}
@@ -1,2 +1,7 @@
 pub mod player;
-pub mod camera;
\ No newline at end of file
+pub mod camera;
+pub mod sprite_sheet;
+pub mod animation;
+pub mod character_sprite;
+pub mod npc;
+pub mod entity;
\ No newline at end of file
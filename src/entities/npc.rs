@@ -0,0 +1,320 @@
+extern crate sdl3;
+
+use std::{fmt, fs, path::{Path, PathBuf}};
+
+use sdl3::rect::Rect;
+use serde::Deserialize;
+
+use crate::{
+    entities::player::interpolate_rect,
+    events::{
+        collision_handler::{CollisionDetector, HitBox},
+        key_handler::Direction
+    },
+    tiles::tile_handler::{Map, TileHandler}
+};
+
+/// Default location of the NPC definitions, next to the maps they stand on.
+pub const NPCS_PATH: &str = "res/maps/npcs.toml";
+
+/// Ticks a wandering NPC stands still between steps, chosen at random (0.5-2 s at 60 ticks/s).
+const WANDER_PAUSE_TICKS: (u32, u32) = (30, 120);
+
+/// Ticks a patrolling NPC waits at each point of its route.
+const PATROL_PAUSE_TICKS: u32 = 30;
+
+// ========== FILE FORMAT ==========
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NpcsFile {
+    #[serde(default)]
+    npc: Vec<NpcDefinition>
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NpcDefinition {
+    name: String,
+    map: String,
+    x: u32,
+    y: u32,
+    sprite: PathBuf,                        // Sprite sheet metadata, relative to the NPC file
+    #[serde(default = "default_facing")]
+    facing: Direction,
+    #[serde(default = "default_speed")]
+    speed: i32,
+    behaviour: BehaviourDefinition,
+    dialogue: Option<String>                // Id of what the NPC says when the player talks to it
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+enum BehaviourDefinition {
+    Wander { radius: u32 },
+    Patrol { route: Vec<[u32; 2]> }         // Tiles as [x, y], walked in order and looped
+}
+
+fn default_facing() -> Direction {
+    Direction::Down
+}
+
+fn default_speed() -> i32 {
+    1
+}
+
+/// Everything that can go wrong while reading the NPC definitions.
+#[derive(Debug)]
+pub enum NpcLoadError {
+    Io { path: PathBuf, message: String },
+    Parse { path: PathBuf, message: String },
+    UnknownMap { path: PathBuf, npc: String, map: String },
+    OutOfBounds { path: PathBuf, npc: String, x: u32, y: u32 },
+    EmptyRoute { path: PathBuf, npc: String },
+    InvalidSpeed { path: PathBuf, npc: String }
+}
+
+impl fmt::Display for NpcLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NpcLoadError::Io { path, message } => {
+                write!(f, "{}: failed to read NPCs: {}", path.display(), message)
+            },
+            NpcLoadError::Parse { path, message } => {
+                write!(f, "{}: invalid NPCs: {}", path.display(), message)
+            },
+            NpcLoadError::UnknownMap { path, npc, map } => {
+                write!(f, "{}: NPC '{}' is placed on unknown map '{}'", path.display(), npc, map)
+            },
+            NpcLoadError::OutOfBounds { path, npc, x, y } => {
+                write!(f, "{}: NPC '{}' uses tile ({}, {}), outside its map", path.display(), npc, x, y)
+            },
+            NpcLoadError::EmptyRoute { path, npc } => {
+                write!(f, "{}: NPC '{}' has an empty patrol route", path.display(), npc)
+            },
+            NpcLoadError::InvalidSpeed { path, npc } => {
+                write!(f, "{}: NPC '{}' needs a speed above 0", path.display(), npc)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NpcLoadError {}
+
+// ========== NPCS ==========
+
+/// How an NPC moves on its own.
+pub enum Behaviour {
+    /// Steps one tile at a time in random directions, never further than
+    /// `radius` tiles from where it was placed.
+    Wander { radius: u32 },
+    /// Walks to each tile of `route` in turn, then starts over.
+    Patrol { route: Vec<(u32, u32)> }
+}
+
+/// A character that moves by itself. Like `Player` it holds no textures;
+/// it is drawn by a `CharacterSprite` loaded from `sprite`.
+///
+/// NPCs collide with solid tiles, the player and each other, and the player
+/// collides with them.
+pub struct Npc {
+    pub name: String,
+    pub map: usize,                 // Index into `TileHandler::maps`
+    pub sprite: PathBuf,
+    pub rect: Rect,                 // World position and size of the sprite
+    pub previous_rect: Rect,        // `rect` before the current tick, for render interpolation
+    pub velocity_x: i32,            // Movement requested this update, in pixels
+    pub velocity_y: i32,
+    pub direction: Direction,
+    pub dialogue: Option<String>,   // Dialogue id, see `DialogueLibrary`
+    speed: i32,                     // Pixels moved per update
+    behaviour: Behaviour,
+    home: (u32, u32),               // Tile the NPC was placed on
+    target: Option<(i32, i32)>,     // World position being walked to
+    waypoint: usize,                // Next route point, for patrols
+    pause: u32,                     // Ticks left standing still
+    rng: fastrand::Rng              // Seeded, so replays see the same moves
+}
+
+impl Npc {
+    /// Returns the NPC's hitbox in world pixels, see `HitBox::for_sprite`.
+    pub fn hit_box(&self) -> HitBox {
+        HitBox::for_sprite(self.rect)
+    }
+
+    /// Whether the NPC tried to move during the last update.
+    pub fn is_moving(&self) -> bool {
+        self.velocity_x != 0 || self.velocity_y != 0
+    }
+
+    /// Position between the previous and current tick, see `Player::interpolated_rect`.
+    pub fn interpolated_rect(&self, alpha: f32) -> Rect {
+        interpolate_rect(self.previous_rect, self.rect, alpha)
+    }
+
+    /// Moves the NPC one tick along its behaviour.
+    ///
+    /// Movement goes one axis at a time, X first, towards a target tile.
+    /// A wandering NPC that is blocked gives up and picks another direction
+    /// later; a patrolling one waits until its way is clear.
+    ///
+    /// # Arguments
+    /// * `map` - Map the NPC walks on
+    /// * `collision_handler` - Resolves movement against solid tiles and other entities
+    /// * `tile_size` - Size of a tile in world pixels
+    pub fn update(&mut self, map: &Map, collision_handler: &CollisionDetector, tile_size: u32) {
+        self.velocity_x = 0;
+        self.velocity_y = 0;
+
+        if self.pause > 0 {
+            self.pause -= 1;
+            return;
+        }
+        if self.target.is_none() {
+            self.target = self.next_target(tile_size);
+        }
+        let Some((target_x, target_y)) = self.target else {
+            self.pause = self.wander_pause();
+            return;
+        };
+
+        self.velocity_x = (target_x - self.rect.x).clamp(-self.speed, self.speed);
+        if self.velocity_x == 0 {
+            self.velocity_y = (target_y - self.rect.y).clamp(-self.speed, self.speed);
+        }
+        self.direction = match (self.velocity_x.signum(), self.velocity_y.signum()) {
+            (1, _) => Direction::Right,
+            (-1, _) => Direction::Left,
+            (_, 1) => Direction::Down,
+            (_, -1) => Direction::Up,
+            _ => self.direction
+        };
+
+        let hit_box = self.hit_box();
+        let (x, y) = collision_handler.resolve(hit_box, self.velocity_x, self.velocity_y, map);
+        let (moved_x, moved_y) = (x - hit_box.x, y - hit_box.y);
+        self.rect.offset(moved_x, moved_y);
+
+        if (self.rect.x, self.rect.y) == (target_x, target_y) {
+            self.target = None;
+            match &self.behaviour {
+                Behaviour::Wander { .. } => self.pause = self.wander_pause(),
+                Behaviour::Patrol { route } => {
+                    self.waypoint = (self.waypoint + 1) % route.len();
+                    self.pause = PATROL_PAUSE_TICKS;
+                }
+            }
+        } else if (moved_x, moved_y) == (0, 0) && matches!(self.behaviour, Behaviour::Wander { .. }) {
+            self.target = None;
+            self.pause = self.wander_pause();
+        }
+    }
+
+    /// World position to walk to next, or `None` if a wandering NPC picked
+    /// a direction that leaves its radius.
+    fn next_target(&mut self, tile_size: u32) -> Option<(i32, i32)> {
+        let (col, row) = match &self.behaviour {
+            Behaviour::Patrol { route } => route[self.waypoint],
+            Behaviour::Wander { radius } => {
+                let radius = *radius as i32;
+                let (home_col, home_row) = (self.home.0 as i32, self.home.1 as i32);
+
+                // The tile the NPC mostly stands on, as it may have stopped between tiles
+                let half = tile_size as i32 / 2;
+                let col = (self.rect.x + half).div_euclid(tile_size as i32);
+                let row = (self.rect.y + half).div_euclid(tile_size as i32);
+                let (col, row) = match self.rng.u8(0..4) {
+                    0 => (col, row - 1),
+                    1 => (col, row + 1),
+                    2 => (col - 1, row),
+                    _ => (col + 1, row)
+                };
+                if col < 0 || row < 0 || (col - home_col).abs() > radius || (row - home_row).abs() > radius {
+                    return None;
+                }
+                (col as u32, row as u32)
+            }
+        };
+        Some(((col * tile_size) as i32, (row * tile_size) as i32))
+    }
+
+    fn wander_pause(&mut self) -> u32 {
+        self.rng.u32(WANDER_PAUSE_TICKS.0..=WANDER_PAUSE_TICKS.1)
+    }
+}
+
+/// Loads NPC definitions and checks them against the loaded maps.
+///
+/// Each NPC's random moves are seeded from its position in the file, so the
+/// same input always plays out the same way.
+///
+/// # Arguments
+/// * `path` - Path to the TOML NPC file
+/// * `tile_handler` - Provides the maps NPCs refer to by name
+pub fn load_npcs(path: impl AsRef<Path>, tile_handler: &TileHandler) -> Result<Vec<Npc>, NpcLoadError> {
+    let path = path.as_ref();
+    let source = fs::read_to_string(path).map_err(|e| NpcLoadError::Io {
+        path: path.to_path_buf(),
+        message: e.to_string()
+    })?;
+    let file: NpcsFile = toml::from_str(&source).map_err(|e| NpcLoadError::Parse {
+        path: path.to_path_buf(),
+        message: e.to_string()
+    })?;
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+    let tile_size = tile_handler.tile_size;
+
+    let mut npcs = Vec::new();
+    for (index, definition) in file.npc.into_iter().enumerate() {
+        let map = tile_handler.map_index(&definition.map).ok_or_else(|| NpcLoadError::UnknownMap {
+            path: path.to_path_buf(),
+            npc: definition.name.clone(),
+            map: definition.map.clone()
+        })?;
+        if definition.speed <= 0 {
+            return Err(NpcLoadError::InvalidSpeed { path: path.to_path_buf(), npc: definition.name });
+        }
+
+        let behaviour = match definition.behaviour {
+            BehaviourDefinition::Wander { radius } => Behaviour::Wander { radius },
+            BehaviourDefinition::Patrol { route } if route.is_empty() => {
+                return Err(NpcLoadError::EmptyRoute { path: path.to_path_buf(), npc: definition.name });
+            },
+            BehaviourDefinition::Patrol { route } => Behaviour::Patrol {
+                route: route.into_iter().map(|[x, y]| (x, y)).collect()
+            }
+        };
+
+        // The starting tile and every route point must lie on the map
+        let target = &tile_handler.maps[map];
+        let mut tiles = vec![(definition.x, definition.y)];
+        if let Behaviour::Patrol { route } = &behaviour {
+            tiles.extend(route);
+        }
+        if let Some(&(x, y)) = tiles.iter().find(|(x, y)| *x >= target.col_len() || *y >= target.row_len()) {
+            return Err(NpcLoadError::OutOfBounds { path: path.to_path_buf(), npc: definition.name, x, y });
+        }
+
+        let rect = Rect::new((definition.x * tile_size) as i32, (definition.y * tile_size) as i32, tile_size, tile_size);
+        npcs.push(Npc {
+            name: definition.name,
+            map,
+            sprite: base_dir.join(definition.sprite),
+            rect,
+            previous_rect: rect,
+            velocity_x: 0,
+            velocity_y: 0,
+            direction: definition.facing,
+            dialogue: definition.dialogue,
+            speed: definition.speed,
+            behaviour,
+            home: (definition.x, definition.y),
+            target: None,
+            waypoint: 0,
+            pause: 0,
+            rng: fastrand::Rng::with_seed(index as u64 + 1)
+        });
+    }
+
+    Ok(npcs)
+}
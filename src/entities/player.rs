@@ -1,250 +1,140 @@
 extern crate sdl3;
 
-use std::collections::HashMap;
+use sdl3::rect::Rect;
 
-use sdl3::{
-    rect::Rect,
-    render::{Canvas, Texture, TextureCreator}, 
-    surface::{Surface}, 
-    video::{Window, WindowContext}
-};
+use crate::{events::{
+    collision_handler::{CollisionDetector, HitBox}, key_handler::{Action, Direction, Keys}
+}, items::inventory::{Inventory, INVENTORY_SLOTS}, tiles::tile_handler::{self, Map}};
 
-use crate::{entities::camera::Camera, events::{
-    collision_handler::{CollisionDetector, HitBox}, key_handler::{ Direction, Keys}
-}, tiles::tile_handler::{self, Map, TileHandler}};
 
-
-/// Manages a sprite animation sequence.
-/// 
-/// Each animation consists of multiple texture frames that cycle to create
-/// the illusion of movement (e.g., a walking animation with 2 frames).
-struct Animations<'a> {
-    frames: Vec<Texture<'a>>,  // Collection of sprite textures
-    current_frame: usize        // Index of the currently displayed frame
-}
-
-impl<'a> Animations<'a> {
-    /// Creates an animation from a list of file paths.
-    /// 
-    /// # Arguments
-    /// * `paths` - Slice of file paths to BMP sprite images
-    /// * `texture_creator` - SDL texture creator tied to the rendering context
-    /// 
-    /// # Returns
-    /// An `Animations` instance with all frames loaded and ready to display
-    fn create_animations(
-        paths: &[&str], 
-        texture_creator: &'a TextureCreator<WindowContext>
-    ) -> Animations<'a> {
-        let mut frames = Vec::new();
-
-        // Load each BMP file and convert it to a GPU texture
-        for path in paths {
-            let texture = Surface::load_bmp(path)
-                .unwrap()
-                .as_texture(texture_creator)
-                .ok()
-                .unwrap();
-            frames.push(texture);
-        }
-        
-        // Start at frame 0
-        Animations { frames, current_frame: 0 }
-    }
-}
+/// Path of the player's sprite sheet metadata.
+pub const PLAYER_SPRITE_SHEET: &str = "res/walking_sprites/boy.toml";
 
 /// Represents the player character in the game.
 /// 
-/// The player has position, movement speed, direction, and direction-specific
-/// walking animations. The sprite automatically changes based on movement direction
-/// and cycles through animation frames while moving.
-pub struct Player<'a> {
-    speed: i32,                                           // Pixels moved per update
-    pub rect: Rect,                                       // Position and size (hitbox)
-    direction: Direction,                                 // Current facing direction
-    walking_animation: HashMap<Direction, Animations<'a>>, // Animation for each direction
-    pub change_sprite: bool,                             // Signal from main loop to advance frame
-    pub on_collision: bool
+/// The player has a world position, velocity, movement speed and direction.
+/// It holds no textures, so it can be simulated without a window; its
+/// sprite is drawn by a `CharacterSprite`.
+pub struct Player {
+    speed: i32,                 // Pixels moved per update
+    pub rect: Rect,             // World position and size of the sprite
+    pub previous_rect: Rect,    // `rect` before the current tick, for render interpolation
+    pub velocity_x: i32,        // Movement requested this update, in pixels
+    pub velocity_y: i32,
+    pub direction: Direction,   // Current facing direction
+    pub on_collision: bool,     // Whether the last move was blocked
+    pub inventory: Inventory    // Items picked up so far
 }
 
-impl<'a> Player<'a> {
-    /// Creates a new player instance with all walking animations loaded.
+impl Player {
+    /// Creates a new player instance.
     /// 
     /// # Arguments
-    /// * `x` - Initial X position in pixels
-    /// * `y` - Initial Y position in pixels
+    /// * `x` - Initial world X position in pixels
+    /// * `y` - Initial world Y position in pixels
     /// * `speed` - Movement speed in pixels per frame
     /// * `tile_size` - Size of the player sprite (width and height)
-    /// * `texture_creator` - SDL texture creator for loading sprite textures
-    /// 
-    /// # Returns
-    /// A fully initialized `Player` ready to be updated and rendered
-    pub fn new(
-        x: i32, 
-        y: i32, 
-        speed: i32, 
-        tile_size: u32,
-        texture_creator: &'a TextureCreator<WindowContext>
-    ) -> Self {
-        // Define sprite file paths for each direction (2 frames per direction)
-        let walking_down_paths = vec![
-            "res/walking_sprites/boy_down_1.bmp", 
-            "res/walking_sprites/boy_down_2.bmp"
-        ];
-        let walking_up_paths = vec![
-            "res/walking_sprites/boy_up_1.bmp",
-            "res/walking_sprites/boy_up_2.bmp"
-        ];
-        let walking_left_paths = vec![
-            "res/walking_sprites/boy_left_1.bmp",
-            "res/walking_sprites/boy_left_2.bmp"
-        ];
-        let walking_right_paths = vec![
-            "res/walking_sprites/boy_right_1.bmp",
-            "res/walking_sprites/boy_right_2.bmp"
-        ];
-
-        // Build the animation HashMap - one animation set per direction
-        let mut walking_animations: HashMap<Direction, Animations> = HashMap::new();
-
-        walking_animations.insert(
-            Direction::Down, 
-            Animations::create_animations(&walking_down_paths, &texture_creator)
-        );
-        walking_animations.insert(
-            Direction::Up, 
-            Animations::create_animations(&walking_up_paths, &texture_creator)
-        );
-        walking_animations.insert(
-            Direction::Left, 
-            Animations::create_animations(&walking_left_paths, &texture_creator)
-        );
-        walking_animations.insert(
-            Direction::Right, 
-            Animations::create_animations(&walking_right_paths, &texture_creator)
-        );
-
+    pub fn new(x: i32, y: i32, speed: i32, tile_size: u32) -> Self {
         Self {
             rect: Rect::new(x, y, tile_size, tile_size),
+            previous_rect: Rect::new(x, y, tile_size, tile_size),
+            velocity_x: 0,
+            velocity_y: 0,
             speed: speed,
             direction: Direction::Down,  // Start facing down
-            walking_animation: walking_animations,
-            change_sprite: false,
-            on_collision: false
-        }
-    }
-    
-    /// Returns a mutable reference to the animation for the current direction.
-    /// Used when we need to modify the animation (e.g., changing frames).
-    fn select_animation_from_direction_mut(&mut self) -> &mut Animations<'a> {
-        match self.direction {
-            Direction::Up => self.walking_animation.get_mut(&Direction::Up).unwrap(),
-            Direction::Down => self.walking_animation.get_mut(&Direction::Down).unwrap(),
-            Direction::Left => self.walking_animation.get_mut(&Direction::Left).unwrap(),
-            Direction::Right => self.walking_animation.get_mut(&Direction::Right).unwrap(),
-        }
-    }
-
-    /// Returns an immutable reference to the animation for the current direction.
-    /// Used when we only need to read animation data (e.g., during rendering).
-    fn select_animation_from_direction(&self) -> &Animations<'a> {
-        match self.direction {
-            Direction::Up => self.walking_animation.get(&Direction::Up).unwrap(),
-            Direction::Down => self.walking_animation.get(&Direction::Down).unwrap(),
-            Direction::Left => self.walking_animation.get(&Direction::Left).unwrap(),
-            Direction::Right => self.walking_animation.get(&Direction::Right).unwrap(),
-        }
-    }
-
-    /// Advances the animation frame when signaled by the main loop.
-    /// 
-    /// This method toggles between frame 0 and frame 1 to create a simple
-    /// two-frame walking animation. The actual timing is controlled externally
-    /// via the `change_sprite` flag set in main.rs.
-    fn sprite_change_handler(&mut self) {
-        // Early exit if no frame change is needed
-        if !self.change_sprite {
-            return;
+            on_collision: false,
+            inventory: Inventory::new(INVENTORY_SLOTS)
         }
-        
-        // Get mutable access to current direction's animation
-        let animations = self.select_animation_from_direction_mut();
-        
-        // Toggle between frame 0 and frame 1
-        animations.current_frame = if animations.current_frame == 1 { 0 } else { 1 };
-        
-        // Reset the signal flag
-        self.change_sprite = false;
     }
 
     /// Updates the player's position and direction based on key input.
     /// 
-    /// This method is called once per game frame. Movement is applied
-    /// independently on each axis, allowing diagonal movement when multiple
-    /// keys are pressed.
+    /// This method is called once per game frame. The velocity requested by
+    /// the keys is passed through the collision detector, which resolves each
+    /// axis separately so the player slides along walls when moving diagonally.
     /// 
     /// # Arguments
-    /// * `keys` - Current state of WASD keys
+    /// * `keys` - Current state of the input actions
+    /// * `map` - Map the player is walking on
+    /// * `collision_handler` - Resolves movement against solid tiles
     pub fn update(
         &mut self, 
-        keys: &mut Keys, 
+        keys: &Keys, 
         map: &Map,
-        collision_handler: &CollisionDetector, 
-        tile_handler: &TileHandler,
-        camera: &Camera
+        collision_handler: &CollisionDetector
     ) {
-        let hit_box = HitBox::new(
-            camera.camera_x.abs() + self.rect.x + 8,
-            camera.camera_y.abs() + self.rect.y + 16,
-            (camera.camera_x.unsigned_abs() + self.rect.x.unsigned_abs()) / tile_handler.tile_size + 1,
-            (camera.camera_y.unsigned_abs() + self.rect.y.unsigned_abs()) / tile_handler.tile_size + 1
-        );
-        println!("Player position: X: {}, Y: {}", self.rect.x, self.rect.y);
-        
-        self.on_collision = collision_handler.check(hit_box, map, &self.direction);
-
-        if keys.w {
+        self.velocity_x = 0;
+        self.velocity_y = 0;
+
+        // Running doubles the walking speed
+        let speed = if keys.is_held(Action::Run) { self.speed * 2 } else { self.speed };
+
+        if keys.is_held(Action::MoveUp) {
             self.direction = Direction::Up;
+            self.velocity_y -= speed;
         }
-        if keys.a {
+        if keys.is_held(Action::MoveLeft) {
             self.direction = Direction::Left;
+            self.velocity_x -= speed;
         }
-        if keys.s {
+        if keys.is_held(Action::MoveDown) {
             self.direction = Direction::Down;
+            self.velocity_y += speed;
         }
-        if keys.d {
+        if keys.is_held(Action::MoveRight) {
             self.direction = Direction::Right;
+            self.velocity_x += speed;
         }
-       
+
+        let hit_box = self.hit_box();
+        let (x, y) = collision_handler.resolve(hit_box, self.velocity_x, self.velocity_y, map);
+        let (moved_x, moved_y) = (x - hit_box.x, y - hit_box.y);
+
+        self.on_collision = moved_x != self.velocity_x || moved_y != self.velocity_y;
+        self.rect.offset(moved_x, moved_y);
     }
 
-    /// Renders the player sprite to the screen.
-    /// 
-    /// The sprite is automatically selected based on the current direction,
-    /// and the animation frame advances when the player is moving. When
-    /// stationary, the player displays the current frame without animating.
-    /// 
-    /// # Arguments
-    /// * `canvas` - SDL canvas to draw on
-    /// * `keys` - Current key states to determine if player is moving
-    pub fn render(&mut self, canvas: &mut Canvas<Window>, keys: &Keys) {
-        // Check if any movement key is pressed
-        if keys.w || keys.a || keys.d || keys.s {
-            // Player is moving: handle animation frame changes
-            self.sprite_change_handler();
-        }
-        
-        // Get the current animation for the player's direction
-        let animations = self.select_animation_from_direction();
-        let image = &animations.frames[animations.current_frame];
-        
-        // Query the texture to get its dimensions
-        let image_attributes = image.query();
-        
-        // Define source rectangle (full texture)
-        let src_rect = Rect::new(0, 0, image_attributes.width, image_attributes.height);
-
-        // Copy the texture to the canvas at the player's position
-        canvas.copy(image, src_rect, self.rect).ok().unwrap();
+    /// Returns the player's hitbox in world pixels, see `HitBox::for_sprite`.
+    pub fn hit_box(&self) -> HitBox {
+        HitBox::for_sprite(self.rect)
+    }
+
+    /// Whether the player tried to move during the last update, even against a wall.
+    pub fn is_moving(&self) -> bool {
+        self.velocity_x != 0 || self.velocity_y != 0
+    }
+
+    /// Returns the world tile (column, row) under the centre of the player's hitbox.
+    pub fn tile_position(&self, tile_size: u32) -> (u32, u32) {
+        let hit_box = self.hit_box();
+        let center_x = hit_box.x + hit_box.width as i32 / 2;
+        let center_y = hit_box.y + hit_box.height as i32 / 2;
+        (center_x.max(0) as u32 / tile_size, center_y.max(0) as u32 / tile_size)
+    }
+
+    /// Places the player on the world tile at `col`, `row`. This is a
+    /// teleport, so it is not interpolated.
+    pub fn move_to_tile(&mut self, col: u32, row: u32, tile_size: u32) {
+        self.rect.set_x((col * tile_size) as i32);
+        self.rect.set_y((row * tile_size) as i32);
+        self.previous_rect = self.rect;
     }
-}
\ No newline at end of file
+
+    /// Position between the previous and current tick, `alpha` being how far
+    /// into the next tick rendering happens (0.0 to 1.0).
+    pub fn interpolated_rect(&self, alpha: f32) -> Rect {
+        interpolate_rect(self.previous_rect, self.rect, alpha)
+    }
+}
+
+/// `current` moved back towards `previous` by `1 - alpha`, for drawing
+/// moving entities between ticks.
+pub fn interpolate_rect(previous: Rect, current: Rect, alpha: f32) -> Rect {
+    let lerp = |from: i32, to: i32| from + ((to - from) as f32 * alpha).round() as i32;
+    Rect::new(
+        lerp(previous.x, current.x),
+        lerp(previous.y, current.y),
+        current.width(),
+        current.height()
+    )
+}
@@ -0,0 +1,224 @@
+extern crate sdl3;
+
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    time::Duration
+};
+
+use sdl3::{
+    rect::Rect,
+    render::{Canvas, FRect},
+    video::Window
+};
+use serde::Deserialize;
+
+use crate::{
+    assets::asset_manager::{AssetManager, TextureHandle},
+    entities::animation::{AnimationClip, PlaybackMode},
+    events::key_handler::Direction
+};
+
+// ========== FILE FORMAT ==========
+
+/// On-disk layout of a sprite sheet's metadata file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SpriteSheetFile {
+    image: PathBuf,                             // Sheet image, relative to the metadata file
+    #[serde(default)]
+    frame: Vec<FrameDefinition>,
+    #[serde(default)]
+    clips: HashMap<String, ClipDefinition>,
+    #[serde(default)]
+    states: HashMap<String, HashMap<Direction, String>>    // State name to the clip for each direction
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FrameDefinition {
+    name: String,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ClipDefinition {
+    frames: Vec<String>,                        // Frame names, in play order
+    durations: Vec<u64>,                        // Milliseconds per frame, or one value for every frame
+    #[serde(default = "default_mode")]
+    mode: PlaybackMode
+}
+
+fn default_mode() -> PlaybackMode {
+    PlaybackMode::Loop
+}
+
+/// Everything that can go wrong while loading a sprite sheet.
+#[derive(Debug)]
+pub enum SpriteSheetError {
+    Io { path: PathBuf, message: String },
+    Parse { path: PathBuf, message: String },
+    DuplicateFrame { path: PathBuf, frame: String },
+    UnknownFrame { path: PathBuf, clip: String, frame: String },
+    FrameOutOfBounds { path: PathBuf, frame: String },
+    MissingState { path: PathBuf, state: String },
+    InvalidDurations { path: PathBuf, clip: String },
+    UnknownClip { path: PathBuf, state: String, clip: String }
+}
+
+impl fmt::Display for SpriteSheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpriteSheetError::Io { path, message } => {
+                write!(f, "{}: failed to read sprite sheet: {}", path.display(), message)
+            },
+            SpriteSheetError::Parse { path, message } => {
+                write!(f, "{}: invalid sprite sheet: {}", path.display(), message)
+            },
+            SpriteSheetError::DuplicateFrame { path, frame } => {
+                write!(f, "{}: frame '{}' is defined more than once", path.display(), frame)
+            },
+            SpriteSheetError::UnknownFrame { path, clip, frame } => {
+                write!(f, "{}: clip '{}' uses unknown frame '{}'", path.display(), clip, frame)
+            },
+            SpriteSheetError::FrameOutOfBounds { path, frame } => {
+                write!(f, "{}: frame '{}' lies outside the sheet image", path.display(), frame)
+            },
+            SpriteSheetError::MissingState { path, state } => {
+                write!(f, "{}: sheet has no animation state '{}'", path.display(), state)
+            },
+            SpriteSheetError::InvalidDurations { path, clip } => {
+                write!(f, "{}: clip '{}' needs one non-zero duration, or one per frame", path.display(), clip)
+            },
+            SpriteSheetError::UnknownClip { path, state, clip } => {
+                write!(f, "{}: state '{}' uses unknown clip '{}'", path.display(), state, clip)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SpriteSheetError {}
+
+// ========== SPRITE SHEET ==========
+
+/// A single texture holding many frames, with named clips of frames.
+///
+/// Frames are addressed by index; clips such as `walk_down` list the frames
+/// to play with their timing, and states such as `walk` pick a clip for
+/// each direction.
+pub struct SpriteSheet {
+    texture: TextureHandle,
+    frames: Vec<Rect>,                          // Source rectangle of each frame
+    clips: HashMap<String, AnimationClip>,
+    states: HashMap<String, HashMap<Direction, String>>
+}
+
+impl SpriteSheet {
+    /// Loads a sprite sheet from its metadata file and the image it names.
+    ///
+    /// An image that fails to load is reported by `assets` and drawn as a
+    /// placeholder; only problems in the metadata are errors here.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the TOML metadata file
+    /// * `assets` - Loads (or reuses) the sheet texture
+    pub fn load(path: impl AsRef<Path>, assets: &mut AssetManager) -> Result<Self, SpriteSheetError> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path).map_err(|e| SpriteSheetError::Io {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+        let file: SpriteSheetFile = toml::from_str(&source).map_err(|e| SpriteSheetError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+
+        // ===== TEXTURE =====
+        let image = path.parent().unwrap_or(Path::new("")).join(&file.image);
+        let texture = assets.load_texture(&image);
+        let query = assets.texture(texture).query();
+        let sheet_bounds = Rect::new(0, 0, query.width, query.height);
+        let check_bounds = !assets.is_missing(texture);
+
+        // ===== FRAMES =====
+        let mut frames = Vec::new();
+        let mut frame_indices = HashMap::new();
+        for frame in file.frame {
+            let rect = Rect::new(frame.x, frame.y, frame.width, frame.height);
+            if check_bounds && (frame.x < 0 || frame.y < 0 || !sheet_bounds.contains_rect(rect)) {
+                return Err(SpriteSheetError::FrameOutOfBounds { path: path.to_path_buf(), frame: frame.name });
+            }
+            if frame_indices.insert(frame.name.clone(), frames.len()).is_some() {
+                return Err(SpriteSheetError::DuplicateFrame { path: path.to_path_buf(), frame: frame.name });
+            }
+            frames.push(rect);
+        }
+
+        // ===== CLIPS =====
+        let mut clips = HashMap::new();
+        for (clip, definition) in file.clips {
+            let mut indices = Vec::new();
+            for frame in definition.frames {
+                let index = *frame_indices.get(&frame).ok_or_else(|| SpriteSheetError::UnknownFrame {
+                    path: path.to_path_buf(),
+                    clip: clip.clone(),
+                    frame: frame.clone()
+                })?;
+                indices.push(index);
+            }
+
+            // A single duration applies to every frame
+            let durations: Vec<Duration> = match definition.durations.as_slice() {
+                [duration] => vec![Duration::from_millis(*duration); indices.len()],
+                durations => durations.iter().map(|duration| Duration::from_millis(*duration)).collect()
+            };
+            if indices.is_empty() || durations.len() != indices.len() || durations.contains(&Duration::ZERO) {
+                return Err(SpriteSheetError::InvalidDurations { path: path.to_path_buf(), clip });
+            }
+            clips.insert(clip, AnimationClip { frames: indices, durations, mode: definition.mode });
+        }
+
+        // ===== STATES =====
+        for (state, directions) in &file.states {
+            if let Some(clip) = directions.values().find(|clip| !clips.contains_key(*clip)) {
+                return Err(SpriteSheetError::UnknownClip {
+                    path: path.to_path_buf(),
+                    state: state.clone(),
+                    clip: clip.clone()
+                });
+            }
+        }
+
+        Ok(Self { texture, frames, clips, states: file.states })
+    }
+
+    /// The clip called `name`.
+    pub fn clip(&self, name: &str) -> Option<&AnimationClip> {
+        self.clips.get(name)
+    }
+
+    /// The clip played for `state` while facing `direction`. States that
+    /// do not define every direction fall back to their `down` clip.
+    pub fn state_clip(&self, state: &str, direction: Direction) -> Option<&AnimationClip> {
+        let directions = self.states.get(state)?;
+        let clip = directions.get(&direction).or_else(|| directions.get(&Direction::Down))?;
+        self.clip(clip)
+    }
+
+    /// Whether the sheet defines `state`.
+    pub fn has_state(&self, state: &str) -> bool {
+        self.states.contains_key(state)
+    }
+
+    /// Copies frame `frame` onto the canvas, stretched to `dest`. The
+    /// placeholder for a missing sheet is drawn whole instead.
+    pub fn draw(&self, canvas: &mut Canvas<Window>, assets: &AssetManager, frame: usize, dest: Rect) {
+        let source = if assets.is_missing(self.texture) { None } else { Some(FRect::from(self.frames[frame])) };
+        canvas.copy(assets.texture(self.texture), source, dest).ok().unwrap();
+    }
+}
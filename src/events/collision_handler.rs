@@ -1,117 +1,151 @@
-use crate::{events::key_handler::Direction, tiles::tile_handler::{Map, Tile, TileHandler}};
+use sdl3::rect::Rect;
+
+use crate::tiles::tile_handler::{Map, Tile, TileHandler};
 
 extern crate sdl3;
 
+// Hitbox placement relative to a character sprite's top-left corner
+const HIT_BOX_OFFSET_X: i32 = 8;
+const HIT_BOX_OFFSET_Y: i32 = 16;
+const HIT_BOX_SIZE: u32 = 32;
+
+/// Axis-aligned box, in world pixels, used to test an entity against the map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct HitBox{
     pub x: i32,
     pub y: i32,
     pub width: u32,
-    pub height: u32,
-    tile_x: u32,
-    tile_y: u32
+    pub height: u32
 }
 
 pub struct CollisionDetector<'a>{
-    tiles: &'a Vec<Tile<'a>>,
-    tile_size: u32
+    tiles: &'a [Tile],
+    tile_size: u32,
+    obstacles: &'a [HitBox]     // Other entities' boxes, solid like walls
 }
 
 impl HitBox {
-    pub fn new(x: i32, y: i32, tile_x: u32, tile_y: u32) -> Self{
-        Self { x, y, width: 32, height: 32, tile_x, tile_y }
+    pub fn new(x: i32, y: i32, width: u32, height: u32) -> Self{
+        Self { x, y, width, height }
+    }
+
+    /// Hitbox of a character drawn at `rect`: a 32x32 box covering the lower
+    /// middle of the sprite, so the head can overlap walls above.
+    pub fn for_sprite(rect: Rect) -> Self {
+        Self::new(rect.x + HIT_BOX_OFFSET_X, rect.y + HIT_BOX_OFFSET_Y, HIT_BOX_SIZE, HIT_BOX_SIZE)
+    }
+
+    /// Whether the two boxes overlap.
+    pub fn intersects(&self, other: &HitBox) -> bool {
+        self.x < other.x + other.width as i32 && other.x < self.x + self.width as i32
+            && self.y < other.y + other.height as i32 && other.y < self.y + self.height as i32
+    }
+
+    /// Position and size along the X (`horizontal`) or Y axis, then across it.
+    fn along(&self, horizontal: bool) -> (i32, i32, i32, i32) {
+        if horizontal {
+            (self.x, self.width as i32, self.y, self.height as i32)
+        } else {
+            (self.y, self.height as i32, self.x, self.width as i32)
+        }
     }
 }
 
 impl<'a> CollisionDetector<'a> {
-    pub fn new(tile_handler: &'a  TileHandler, tile_size: u32) -> Self{
-        Self { tiles: &tile_handler.tiles , tile_size}
+    pub fn new(tile_handler: &'a TileHandler, tile_size: u32) -> Self{
+        Self { tiles: &tile_handler.tiles , tile_size, obstacles: &[]}
+    }
+
+    /// Also blocks movement into `obstacles`, such as NPCs or the player.
+    pub fn with_obstacles(self, obstacles: &'a [HitBox]) -> Self {
+        Self { obstacles, ..self }
     }
 
-    pub fn check(&self, hit_box: HitBox, map: &Map, direction: &Direction) -> bool{
-        let result: Result<bool, &str> = (|| {
-            let top_tile = map.map
-                .get(hit_box.tile_y as usize + 1).ok_or("Top Tile Failed")?
-                .get(hit_box.tile_x as usize).ok_or("Top Tile Failed")?;
-            let bottom_tile = map.map
-                .get(hit_box.tile_y as usize - 1).ok_or("Bottom Tile Failed")?
-                .get(hit_box.tile_x as usize).ok_or("Bottom Tile Failed")?;
-            let left_tile = map.map
-                .get(hit_box.tile_y as usize).ok_or("Left Tile Failed")?
-                .get(hit_box.tile_x as usize - 1 ).ok_or("Left Tile Failed")?;
-            let right_tile = map.map
-                .get(hit_box.tile_y as usize).ok_or("Right Tile Failed")?
-                .get(hit_box.tile_x as usize + 1).ok_or("Right Tile Failed")?;
-
-            match direction{
-                Direction::Up => {
-                    if self.tiles[top_tile.index as usize].immovable {
-                        if hit_box.x + hit_box.width as i32 >= top_tile.x &&
-                           hit_box.x <= top_tile.x + self.tile_size as i32 &&
-                           hit_box.y + hit_box.height as i32 >= top_tile.y&&
-                           hit_box.y <= top_tile.y + self.tile_size as i32
-                        {
-                            return Ok(true);
-                        }    
-                    }
-                },
-                Direction::Down => {
-                    if self.tiles[bottom_tile.index as usize].immovable {
-                        if hit_box.x + hit_box.width as i32 >= bottom_tile.x &&
-                           hit_box.x <= bottom_tile.x + self.tile_size as i32 &&
-                           hit_box.y + hit_box.height as i32 >= bottom_tile.y &&
-                           hit_box.y <= bottom_tile.y + self.tile_size as i32
-                        {
-                            return Ok(true);
-                        }   
-                    }
-                },
-                Direction::Left => {
-                    if self.tiles[left_tile.index as usize].immovable {
-                        if hit_box.x + hit_box.width as i32 >= left_tile.x &&
-                           hit_box.x <= left_tile.x + self.tile_size as i32 &&
-                           hit_box.y + hit_box.height as i32 >= left_tile.y &&
-                           hit_box.y <= left_tile.y + self.tile_size as i32
-                        {
-                            return Ok(true);
-                        }   
-                    }
-                },
-                Direction::Right => {
-                    if self.tiles[right_tile.index as usize].immovable {
-                        if hit_box.x + hit_box.width as i32 >= right_tile.x &&
-                           hit_box.x <= right_tile.x + self.tile_size as i32 &&
-                           hit_box.y + hit_box.height as i32 >= right_tile.y &&
-                           hit_box.y <= right_tile.y + self.tile_size as i32
-                        {
-                            return Ok(true);
-                        }   
-                    }
-                },
+    /// Moves `hit_box` by (`dx`, `dy`) and stops it at the first solid tile or obstacle.
+    ///
+    /// The X and Y axes are resolved separately, X first, so a box pushed
+    /// diagonally into a wall keeps sliding along it. On each axis every tile
+    /// the box sweeps across is tested, not just the one in front of it.
+    /// Cells outside the map count as solid.
+    ///
+    /// # Arguments
+    /// * `hit_box` - Current box in world pixels
+    /// * `dx`, `dy` - Proposed movement in pixels
+    /// * `map` - Map whose collision-bearing layers are tested
+    ///
+    /// # Returns
+    /// The box's new top-left corner
+    pub fn resolve(&self, hit_box: HitBox, dx: i32, dy: i32, map: &Map) -> (i32, i32) {
+        let x = self.resolve_axis(hit_box, dx, true, map);
+        let moved = HitBox { x, ..hit_box };
+        let y = self.resolve_axis(moved, dy, false, map);
+        (x, y)
+    }
+
+    /// Resolves movement along one axis and returns the new coordinate on it.
+    fn resolve_axis(&self, hit_box: HitBox, delta: i32, horizontal: bool, map: &Map) -> i32 {
+        let tile_size = self.tile_size as i32;
+
+        // Position and size along the moving axis, and the span across it
+        let (start, size, cross_start, cross_size) = hit_box.along(horizontal);
+        if delta == 0 {
+            return start;
+        }
+
+        // Tiles swept between the current and the proposed position
+        let target = start + delta;
+        let sweep_first = start.min(target).div_euclid(tile_size);
+        let sweep_last = (start.max(target) + size - 1).div_euclid(tile_size);
+        let cross_first = cross_start.div_euclid(tile_size);
+        let cross_last = (cross_start + cross_size - 1).div_euclid(tile_size);
+
+        let mut resolved = target;
+        for along in sweep_first..=sweep_last {
+            let tile_start = along * tile_size;
+            let tile_end = tile_start + tile_size;
+
+            // Tiles the box already overlaps never block, so it can back out
+            let ahead = if delta > 0 { tile_start >= start + size } else { tile_end <= start };
+            if !ahead {
+                continue;
+            }
+
+            let blocked = (cross_first..=cross_last).any(|across| {
+                let (col, row) = if horizontal { (along, across) } else { (across, along) };
+                self.is_blocking(map, row, col)
+            });
+            if blocked {
+                resolved = if delta > 0 {
+                    resolved.min(tile_start - size)
+                } else {
+                    resolved.max(tile_end)
+                };
+            }
+        }
+
+        // Entities ahead on the same line stop the box at their edge. As
+        // with tiles, boxes it already overlaps never block
+        for obstacle in self.obstacles {
+            let (other_start, other_size, other_cross, other_cross_size) = obstacle.along(horizontal);
+            if other_cross >= cross_start + cross_size || other_cross + other_cross_size <= cross_start {
+                continue;
+            }
+            if delta > 0 && other_start >= start + size {
+                resolved = resolved.min(other_start - size);
+            } else if delta < 0 && other_start + other_size <= start {
+                resolved = resolved.max(other_start + other_size);
             }
-            Ok(false)
-        })();
-        
-        match result{
-            Ok(v) => {v},
-            Err(e) => {
-               if * direction == Direction::Up && e == "Top Tile Failed"{
-                    return true;
-               }
-               else if * direction == Direction::Down && e == "Bottom Tile Failed"{
-                    return true;
-               }
-               else if * direction == Direction::Left && e == "Left Tile Failed"{
-                    return true;
-               }
-               else if * direction == Direction::Right && e == "Right Tile Failed"{
-                    return true;
-               }
-               else{
-                    return false;
-               }
-            },
         }
 
+        resolved
     }
 
-}
\ No newline at end of file
+    /// Whether the cell at `row`, `col` blocks movement. Cells outside the map do.
+    fn is_blocking(&self, map: &Map, row: i32, col: i32) -> bool {
+        if row < 0 || col < 0 {
+            return true;
+        }
+        map.is_solid(row as usize, col as usize, self.tiles).unwrap_or(true)
+    }
+
+}
@@ -0,0 +1,96 @@
+extern crate sdl3;
+
+use std::collections::HashMap;
+
+use sdl3::{
+    event::Event,
+    gamepad::{Axis, Gamepad},
+    GamepadSubsystem
+};
+
+use crate::events::{
+    key_bindings::KeyBindings,
+    key_handler::{Action, InputSource, Keys}
+};
+
+/// Opens gamepads as they are plugged in and turns their buttons and left
+/// stick into the same actions the keyboard produces.
+///
+/// Gamepads connected before startup are reported by SDL as "added" events
+/// on the first poll, so they are picked up the same way as hot-plugged ones.
+pub struct GamepadHandler {
+    subsystem: GamepadSubsystem,
+    gamepads: HashMap<u32, Gamepad>     // Open gamepads by joystick id
+}
+
+impl GamepadHandler {
+    pub fn new(subsystem: GamepadSubsystem) -> Self {
+        Self { subsystem, gamepads: HashMap::new() }
+    }
+
+    /// Processes one SDL event. Non-gamepad events are ignored.
+    ///
+    /// # Arguments
+    /// * `event` - SDL event to process
+    /// * `bindings` - Which buttons trigger which actions, and the stick deadzone
+    /// * `keys` - Action state shared with the keyboard
+    pub fn handle_event(&mut self, event: &Event, bindings: &KeyBindings, keys: &mut Keys) {
+        match *event {
+            // ===== HOT-PLUG =====
+            Event::ControllerDeviceAdded { which, .. } => {
+                match self.subsystem.open(which) {
+                    Ok(gamepad) => {
+                        println!("Gamepad connected: {}", gamepad.name().unwrap_or_default());
+                        self.gamepads.insert(which, gamepad);
+                    },
+                    Err(e) => println!("Failed to open gamepad {}: {}", which, e)
+                }
+            },
+            Event::ControllerDeviceRemoved { which, .. } => {
+                // Dropping the gamepad closes it; anything it held is let go
+                self.gamepads.remove(&which);
+                keys.release_gamepad(which);
+                println!("Gamepad disconnected");
+            },
+
+            // ===== BUTTONS =====
+            Event::ControllerButtonDown { which, button, .. } => {
+                for action in bindings.actions_for_button(button) {
+                    keys.press(action, InputSource::GamepadButton(which, button));
+                }
+            },
+            // Released from every action it holds, including ones it was
+            // unbound from while held down
+            Event::ControllerButtonUp { which, button, .. } => {
+                keys.release_source(InputSource::GamepadButton(which, button));
+            },
+
+            // ===== LEFT STICK =====
+            // Each half of an axis acts like a direction key that is held
+            // while the stick is pushed past the deadzone
+            Event::ControllerAxisMotion { which, axis, value, .. } => {
+                let (negative, positive) = match axis {
+                    Axis::LeftX => (Action::MoveLeft, Action::MoveRight),
+                    Axis::LeftY => (Action::MoveUp, Action::MoveDown),
+                    _ => return
+                };
+                let deadzone = bindings.stick_deadzone as i32;
+                let value = value as i32;
+
+                for (action, pushed_positive, pushed) in [
+                    (negative, false, value < -deadzone),
+                    (positive, true, value > deadzone)
+                ] {
+                    let source = InputSource::GamepadStick(which, axis, pushed_positive);
+                    if pushed {
+                        keys.press(action, source);
+                    } else {
+                        keys.release(action, source);
+                    }
+                }
+            },
+
+            _ => ()
+        }
+    }
+}
@@ -0,0 +1,245 @@
+extern crate sdl3;
+
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf}
+};
+
+use sdl3::{gamepad::Button, keyboard::Keycode};
+use serde::{Deserialize, Serialize};
+
+use crate::events::key_handler::Action;
+
+/// Default location of the key bindings file, relative to the working directory.
+pub const KEY_BINDINGS_PATH: &str = "config/key_bindings.toml";
+
+/// Left stick deflection (out of 32767) needed before it counts as a direction.
+const DEFAULT_STICK_DEADZONE: i16 = 8000;
+
+/// On-disk layout: action name to a list of SDL key names, plus the
+/// gamepad section.
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct KeyBindingsFile {
+    #[serde(default)]
+    bindings: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    gamepad: GamepadBindingsFile
+}
+
+/// Gamepad section: stick deadzone and action name to SDL button names.
+#[derive(Deserialize, Serialize, Default)]
+#[serde(deny_unknown_fields)]
+struct GamepadBindingsFile {
+    #[serde(default)]
+    deadzone: Option<i16>,
+    #[serde(default)]
+    bindings: BTreeMap<String, Vec<String>>
+}
+
+/// Everything that can go wrong while reading or writing key bindings.
+#[derive(Debug)]
+pub enum KeyBindingsError {
+    Io { path: PathBuf, message: String },
+    Parse { path: PathBuf, message: String },
+    UnknownAction { path: PathBuf, action: String },
+    UnknownKey { path: PathBuf, action: String, key: String },
+    UnknownButton { path: PathBuf, action: String, button: String }
+}
+
+impl fmt::Display for KeyBindingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyBindingsError::Io { path, message } => {
+                write!(f, "{}: failed to access key bindings: {}", path.display(), message)
+            },
+            KeyBindingsError::Parse { path, message } => {
+                write!(f, "{}: invalid key bindings: {}", path.display(), message)
+            },
+            KeyBindingsError::UnknownAction { path, action } => {
+                write!(f, "{}: '{}' is not an action", path.display(), action)
+            },
+            KeyBindingsError::UnknownKey { path, action, key } => {
+                write!(f, "{}: action '{}' is bound to unknown key '{}'", path.display(), action, key)
+            },
+            KeyBindingsError::UnknownButton { path, action, button } => {
+                write!(f, "{}: action '{}' is bound to unknown gamepad button '{}'", path.display(), action, button)
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeyBindingsError {}
+
+/// Maps each `Action` to the keys and gamepad buttons that trigger it.
+///
+/// An action may have several keys and a key may trigger several actions.
+/// The gamepad's left stick always drives the movement actions once pushed
+/// past `stick_deadzone`.
+pub struct KeyBindings {
+    bindings: BTreeMap<Action, Vec<Keycode>>,
+    gamepad_bindings: BTreeMap<Action, Vec<Button>>,
+    pub stick_deadzone: i16
+}
+
+impl Default for KeyBindings {
+    /// WASD and the arrow keys move, Shift runs, E/Space/Return interact,
+    /// I/Tab open the inventory and Escape opens the menu. On a gamepad the
+    /// D-pad moves, the bottom face button interacts, the right one runs,
+    /// the top one opens the inventory and Start opens the menu.
+    fn default() -> Self {
+        let mut bindings = BTreeMap::new();
+        bindings.insert(Action::MoveUp, vec![Keycode::W, Keycode::Up]);
+        bindings.insert(Action::MoveDown, vec![Keycode::S, Keycode::Down]);
+        bindings.insert(Action::MoveLeft, vec![Keycode::A, Keycode::Left]);
+        bindings.insert(Action::MoveRight, vec![Keycode::D, Keycode::Right]);
+        bindings.insert(Action::Run, vec![Keycode::LShift, Keycode::RShift]);
+        bindings.insert(Action::Interact, vec![Keycode::E, Keycode::Space, Keycode::Return]);
+        bindings.insert(Action::Inventory, vec![Keycode::I, Keycode::Tab]);
+        bindings.insert(Action::Menu, vec![Keycode::Escape]);
+
+        let mut gamepad_bindings = BTreeMap::new();
+        gamepad_bindings.insert(Action::MoveUp, vec![Button::DPadUp]);
+        gamepad_bindings.insert(Action::MoveDown, vec![Button::DPadDown]);
+        gamepad_bindings.insert(Action::MoveLeft, vec![Button::DPadLeft]);
+        gamepad_bindings.insert(Action::MoveRight, vec![Button::DPadRight]);
+        gamepad_bindings.insert(Action::Run, vec![Button::East]);
+        gamepad_bindings.insert(Action::Interact, vec![Button::South]);
+        gamepad_bindings.insert(Action::Inventory, vec![Button::North]);
+        gamepad_bindings.insert(Action::Menu, vec![Button::Start]);
+
+        Self { bindings, gamepad_bindings, stick_deadzone: DEFAULT_STICK_DEADZONE }
+    }
+}
+
+impl KeyBindings {
+    /// Loads bindings from `path`, or writes the defaults there if the file
+    /// does not exist yet. Actions missing from the file keep their defaults.
+    pub fn load_or_default(path: impl AsRef<Path>) -> Result<Self, KeyBindingsError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            let bindings = Self::default();
+            bindings.save(path)?;
+            return Ok(bindings);
+        }
+        Self::load(path)
+    }
+
+    /// Loads bindings from `path`. Actions missing from the file keep their defaults.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, KeyBindingsError> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path).map_err(|e| KeyBindingsError::Io {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+        let file: KeyBindingsFile = toml::from_str(&source).map_err(|e| KeyBindingsError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+
+        let mut key_bindings = Self::default();
+        for (action_name, key_names) in file.bindings {
+            let action = Action::from_name(&action_name).ok_or_else(|| KeyBindingsError::UnknownAction {
+                path: path.to_path_buf(),
+                action: action_name.clone()
+            })?;
+
+            let mut keys = Vec::new();
+            for key_name in key_names {
+                let keycode = Keycode::from_name(&key_name).ok_or_else(|| KeyBindingsError::UnknownKey {
+                    path: path.to_path_buf(),
+                    action: action_name.clone(),
+                    key: key_name.clone()
+                })?;
+                keys.push(keycode);
+            }
+            key_bindings.bindings.insert(action, keys);
+        }
+
+        // ===== GAMEPAD =====
+        if let Some(deadzone) = file.gamepad.deadzone {
+            key_bindings.stick_deadzone = deadzone;
+        }
+        for (action_name, button_names) in file.gamepad.bindings {
+            let action = Action::from_name(&action_name).ok_or_else(|| KeyBindingsError::UnknownAction {
+                path: path.to_path_buf(),
+                action: action_name.clone()
+            })?;
+
+            let mut buttons = Vec::new();
+            for button_name in button_names {
+                let button = Button::from_string(&button_name).ok_or_else(|| KeyBindingsError::UnknownButton {
+                    path: path.to_path_buf(),
+                    action: action_name.clone(),
+                    button: button_name.clone()
+                })?;
+                buttons.push(button);
+            }
+            key_bindings.gamepad_bindings.insert(action, buttons);
+        }
+
+        Ok(key_bindings)
+    }
+
+    /// Writes the bindings to `path`, creating its directory if needed.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), KeyBindingsError> {
+        let path = path.as_ref();
+        let io_error = |e: std::io::Error| KeyBindingsError::Io {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        };
+
+        let file = KeyBindingsFile {
+            bindings: self.bindings.iter()
+                .map(|(action, keys)| {
+                    (action.name().to_string(), keys.iter().map(|key| key.name()).collect())
+                })
+                .collect(),
+            gamepad: GamepadBindingsFile {
+                deadzone: Some(self.stick_deadzone),
+                bindings: self.gamepad_bindings.iter()
+                    .map(|(action, buttons)| {
+                        (action.name().to_string(), buttons.iter().map(|button| button.string()).collect())
+                    })
+                    .collect()
+            }
+        };
+        let source = toml::to_string_pretty(&file).map_err(|e| KeyBindingsError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(io_error)?;
+        }
+        fs::write(path, source).map_err(io_error)
+    }
+
+    /// Keys bound to `action`.
+    pub fn keys_for(&self, action: Action) -> &[Keycode] {
+        self.bindings.get(&action).map_or(&[], |keys| keys.as_slice())
+    }
+
+    /// Binds `action` to exactly `keys`, replacing its previous keys.
+    /// Gamepad buttons are left alone.
+    pub fn set_keys(&mut self, action: Action, keys: Vec<Keycode>) {
+        self.bindings.insert(action, keys);
+    }
+
+    /// Actions triggered by `keycode`.
+    pub fn actions_for(&self, keycode: Keycode) -> Vec<Action> {
+        self.bindings.iter()
+            .filter(|(_, keys)| keys.contains(&keycode))
+            .map(|(action, _)| *action)
+            .collect()
+    }
+
+    /// Actions triggered by the gamepad `button`.
+    pub fn actions_for_button(&self, button: Button) -> Vec<Action> {
+        self.gamepad_bindings.iter()
+            .filter(|(_, buttons)| buttons.contains(&button))
+            .map(|(action, _)| *action)
+            .collect()
+    }
+}
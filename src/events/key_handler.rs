@@ -1,24 +1,154 @@
 extern crate sdl3;
+use std::collections::{HashMap, HashSet};
+
 use sdl3::{
     keyboard::Keycode,
-    event::Event
+    event::Event,
+    gamepad::{Axis, Button}
 };
+use serde::{Deserialize, Serialize};
+
+use crate::events::key_bindings::KeyBindings;
+
+/// Something the player can do, independent of which key triggers it.
+#[derive(Eq, Hash, PartialEq, Clone, Copy, Debug, PartialOrd, Ord)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Run,
+    Interact,
+    Inventory,
+    Menu
+}
+
+impl Action {
+    /// Every action, in the order they are listed in the bindings file.
+    pub const ALL: [Action; 8] = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Run,
+        Action::Interact,
+        Action::Inventory,
+        Action::Menu
+    ];
+
+    /// Name used for the action in config files.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::MoveLeft => "move_left",
+            Action::MoveRight => "move_right",
+            Action::Run => "run",
+            Action::Interact => "interact",
+            Action::Inventory => "inventory",
+            Action::Menu => "menu"
+        }
+    }
 
-/// Tracks the pressed/released state of the WASD movement keys.
-/// 
-/// Each boolean represents whether a specific key is currently held down.
-/// This allows for smooth, simultaneous multi-directional input (e.g., diagonal movement).
+    /// Parses a name produced by `Action::name`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Action::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+/// A physical input that can hold an action down.
+#[derive(Eq, Hash, PartialEq, Clone, Copy, Debug)]
+pub enum InputSource {
+    Key(Keycode),
+    GamepadButton(u32, Button),         // Joystick id and button
+    GamepadStick(u32, Axis, bool),      // Joystick id, axis and whether it is pushed positive
+    Simulated                           // Held by code, e.g. a headless simulation
+}
+
+impl InputSource {
+    /// The joystick id of a gamepad input, `None` for the keyboard.
+    pub fn gamepad(&self) -> Option<u32> {
+        match self {
+            InputSource::Key(_) | InputSource::Simulated => None,
+            InputSource::GamepadButton(which, _) | InputSource::GamepadStick(which, _, _) => Some(*which)
+        }
+    }
+}
+
+/// Tracks which actions are held down.
+///
+/// An action stays held while any of the inputs bound to it is down, so
+/// holding W and the Up arrow (or the D-pad) together and releasing one
+/// keeps moving. This allows for smooth, simultaneous multi-directional
+/// input (e.g., diagonal movement).
+#[derive(Default)]
 pub struct Keys {
-    pub w: bool,  // Move up
-    pub a: bool,  // Move left
-    pub s: bool,  // Move down
-    pub d: bool   // Move right
+    held: HashMap<Action, HashSet<InputSource>>    // Inputs currently holding each action down
+}
+
+impl Keys {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keys with exactly `actions` held, as if by a script rather than a
+    /// device. Used to drive `World::step` without SDL events.
+    pub fn holding(actions: &[Action]) -> Self {
+        let mut keys = Self::new();
+        for action in actions {
+            keys.press(*action, InputSource::Simulated);
+        }
+        keys
+    }
+
+    /// Whether the action is currently held down.
+    pub fn is_held(&self, action: Action) -> bool {
+        self.held.get(&action).is_some_and(|keys| !keys.is_empty())
+    }
+
+    /// Every action currently held, in `Action::ALL` order.
+    pub fn held_actions(&self) -> Vec<Action> {
+        Action::ALL.into_iter().filter(|action| self.is_held(*action)).collect()
+    }
+
+    /// Whether any movement action is held.
+    pub fn is_moving(&self) -> bool {
+        [Action::MoveUp, Action::MoveDown, Action::MoveLeft, Action::MoveRight]
+            .into_iter()
+            .any(|action| self.is_held(action))
+    }
+
+    pub(crate) fn press(&mut self, action: Action, source: InputSource) {
+        self.held.entry(action).or_default().insert(source);
+    }
+
+    pub(crate) fn release(&mut self, action: Action, source: InputSource) {
+        if let Some(sources) = self.held.get_mut(&action) {
+            sources.remove(&source);
+        }
+    }
+
+    /// Releases every action held by `source`.
+    pub(crate) fn release_source(&mut self, source: InputSource) {
+        for sources in self.held.values_mut() {
+            sources.remove(&source);
+        }
+    }
+
+    /// Releases everything held by the gamepad with joystick id `which`,
+    /// e.g. when it is unplugged mid-press.
+    pub(crate) fn release_gamepad(&mut self, which: u32) {
+        for sources in self.held.values_mut() {
+            sources.retain(|source| source.gamepad() != Some(which));
+        }
+    }
 }
 
 /// Represents the four cardinal directions the player can face.
 /// Used as keys in the animation HashMap to select appropriate sprite sets.
-#[derive(Eq, Hash, PartialEq)]
-#[derive(Debug)]
+#[derive(Eq, Hash, PartialEq, Clone, Copy)]
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Direction {
     Up,
     Down,
@@ -26,54 +156,81 @@ pub enum Direction {
     Right,
 }
 
+impl Direction {
+    /// The direction facing back the other way.
+    pub fn opposite(&self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left
+        }
+    }
+}
+
+/// Turns held actions into one-shot presses by comparing the actions held
+/// this tick with those held the tick before.
+///
+/// Working from held state means presses replay exactly, since replays
+/// record which actions were held on every tick.
+#[derive(Default)]
+pub struct PressTracker {
+    previous: Vec<Action>,      // Held last tick
+    pressed: Vec<Action>        // Held this tick but not last tick
+}
+
+impl PressTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-/// Processes SDL keyboard events and updates the key state accordingly.
-/// 
-/// This function handles both KeyDown and KeyUp events for the WASD keys,
+    /// Records this tick's input. Call once per tick, before `was_pressed`.
+    pub fn update(&mut self, keys: &Keys) {
+        let held = keys.held_actions();
+        self.pressed = held.iter().copied().filter(|action| !self.previous.contains(action)).collect();
+        self.previous = held;
+    }
+
+    /// Whether `action` went down this tick.
+    pub fn was_pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+}
+
+
+/// Processes SDL keyboard events and updates the action state accordingly.
+///
+/// This function handles both KeyDown and KeyUp events for every bound key,
 /// maintaining accurate state even when keys are pressed simultaneously.
-/// 
+///
 /// # Arguments
 /// * `event` - SDL event to process (typically from the event pump)
+/// * `bindings` - Which keys trigger which actions
 /// * `keys` - Mutable reference to the Keys struct to update
-/// 
+///
 /// # Example Flow
-/// 1. User presses W key → KeyDown event → keys.w = true
-/// 2. Player moves up based on keys.w being true
-/// 3. User releases W key → KeyUp event → keys.w = false
+/// 1. User presses W key → KeyDown event → `MoveUp` is held
+/// 2. Player moves up while `MoveUp` is held
+/// 3. User releases W key → KeyUp event → `MoveUp` is released
 /// 4. Player stops moving up
-pub fn key_handler(event: Event, keys: &mut Keys) {
+pub fn key_handler(event: Event, bindings: &KeyBindings, keys: &mut Keys) {
     match event {
         // ===== KEY PRESS HANDLERS =====
-        // Set the corresponding key state to true when pressed
-        Event::KeyDown { keycode: Some(Keycode::W), ..} => {
-            keys.w = true;
-        },
-        Event::KeyDown { keycode: Some(Keycode::A), ..} => {
-            keys.a = true;
-        },
-        Event::KeyDown { keycode: Some(Keycode::S), ..} => {
-            keys.s = true;
+        // Hold every action bound to the pressed key
+        Event::KeyDown { keycode: Some(keycode), ..} => {
+            for action in bindings.actions_for(keycode) {
+                keys.press(action, InputSource::Key(keycode));
+            }
         },
-        Event::KeyDown { keycode: Some(Keycode::D), ..} => {
-            keys.d = true;
-        },
-        
+
         // ===== KEY RELEASE HANDLERS =====
-        // Set the corresponding key state to false when released
-        Event::KeyUp { keycode: Some(Keycode::W), ..} => {
-            keys.w = false;
-        },
-        Event::KeyUp { keycode: Some(Keycode::A), ..} => {
-            keys.a = false;
-        },
-        Event::KeyUp { keycode: Some(Keycode::S), ..} => {
-            keys.s = false;
-        },
-        Event::KeyUp { keycode: Some(Keycode::D), ..} => {
-            keys.d = false;
+        // Release every action the key holds, including ones it was
+        // unbound from while held down
+        Event::KeyUp { keycode: Some(keycode), ..} => {
+            keys.release_source(InputSource::Key(keycode));
         }
-        
+
         // Ignore all other events (mouse, window events, etc.)
         _ => ()
     }
-}
\ No newline at end of file
+}
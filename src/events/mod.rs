@@ -1,2 +1,6 @@
 pub mod key_handler;
-pub mod collision_handler;
\ No newline at end of file
+pub mod key_bindings;
+pub mod gamepad_handler;
+pub mod collision_handler;
+pub mod warp_handler;
+pub mod replay;
\ No newline at end of file
@@ -0,0 +1,208 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf}
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::events::key_handler::{Action, Keys};
+
+/// Version written to new replay files. Files with any other version are rejected.
+const REPLAY_VERSION: u32 = 1;
+
+// ========== FILE FORMAT ==========
+
+/// On-disk layout of a replay: where the run started and the held actions
+/// for every tick, run-length encoded.
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct ReplayFile {
+    version: u32,
+    map: String,
+    start_x: i32,
+    start_y: i32,
+    tick_rate: u32,
+    #[serde(default)]
+    span: Vec<ReplaySpan>
+}
+
+/// `ticks` consecutive ticks during which exactly the actions in `held` were held.
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct ReplaySpan {
+    ticks: u32,
+    #[serde(default)]
+    held: Vec<String>
+}
+
+/// Everything that can go wrong while reading or writing a replay.
+#[derive(Debug)]
+pub enum ReplayError {
+    Io { path: PathBuf, message: String },
+    Parse { path: PathBuf, message: String },
+    UnsupportedVersion { path: PathBuf, version: u32 },
+    UnknownAction { path: PathBuf, action: String }
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io { path, message } => {
+                write!(f, "{}: failed to access replay: {}", path.display(), message)
+            },
+            ReplayError::Parse { path, message } => {
+                write!(f, "{}: invalid replay: {}", path.display(), message)
+            },
+            ReplayError::UnsupportedVersion { path, version } => {
+                write!(f, "{}: replay version {} is not supported (expected {})", path.display(), version, REPLAY_VERSION)
+            },
+            ReplayError::UnknownAction { path, action } => {
+                write!(f, "{}: '{}' is not an action", path.display(), action)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+// ========== RECORDING ==========
+
+/// Records the actions held on every tick so a run can be played back exactly.
+pub struct ReplayRecorder {
+    map: String,
+    start_x: i32,
+    start_y: i32,
+    tick_rate: u32,
+    spans: Vec<(u32, Vec<Action>)>      // Tick count and the actions held for them
+}
+
+impl ReplayRecorder {
+    /// Starts a recording.
+    ///
+    /// # Arguments
+    /// * `map` - Name of the map the player starts on
+    /// * `start_x`, `start_y` - Player's starting world position
+    /// * `tick_rate` - Fixed updates per second the run is simulated at
+    pub fn new(map: &str, start_x: i32, start_y: i32, tick_rate: u32) -> Self {
+        Self { map: map.to_string(), start_x, start_y, tick_rate, spans: Vec::new() }
+    }
+
+    /// Records the input fed into one tick. Call once per `World::step`.
+    pub fn record(&mut self, keys: &Keys) {
+        let held = keys.held_actions();
+        match self.spans.last_mut() {
+            Some((ticks, last)) if *last == held => *ticks += 1,
+            _ => self.spans.push((1, held))
+        }
+    }
+
+    /// Writes the recording to `path`, creating its directory if needed.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ReplayError> {
+        let path = path.as_ref();
+        let io_error = |e: std::io::Error| ReplayError::Io {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        };
+
+        let file = ReplayFile {
+            version: REPLAY_VERSION,
+            map: self.map.clone(),
+            start_x: self.start_x,
+            start_y: self.start_y,
+            tick_rate: self.tick_rate,
+            span: self.spans.iter()
+                .map(|(ticks, held)| ReplaySpan {
+                    ticks: *ticks,
+                    held: held.iter().map(|action| action.name().to_string()).collect()
+                })
+                .collect()
+        };
+        let source = toml::to_string_pretty(&file).map_err(|e| ReplayError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(io_error)?;
+        }
+        fs::write(path, source).map_err(io_error)
+    }
+}
+
+// ========== PLAYBACK ==========
+
+/// A loaded replay, handing out the recorded input one tick at a time.
+pub struct Replay {
+    pub map: String,
+    pub start_x: i32,
+    pub start_y: i32,
+    pub tick_rate: u32,
+    spans: Vec<(u32, Vec<Action>)>,
+    span: usize,        // Span currently being played
+    tick: u32           // Ticks already played from that span
+}
+
+impl Replay {
+    /// Loads and validates the replay at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path).map_err(|e| ReplayError::Io {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+        let file: ReplayFile = toml::from_str(&source).map_err(|e| ReplayError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+        if file.version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion { path: path.to_path_buf(), version: file.version });
+        }
+        if file.tick_rate == 0 {
+            return Err(ReplayError::Parse {
+                path: path.to_path_buf(),
+                message: "tick_rate must be greater than 0".to_string()
+            });
+        }
+
+        let mut spans = Vec::new();
+        for span in file.span {
+            let mut held = Vec::new();
+            for action_name in span.held {
+                let action = Action::from_name(&action_name).ok_or_else(|| ReplayError::UnknownAction {
+                    path: path.to_path_buf(),
+                    action: action_name.clone()
+                })?;
+                held.push(action);
+            }
+            spans.push((span.ticks, held));
+        }
+
+        Ok(Self {
+            map: file.map,
+            start_x: file.start_x,
+            start_y: file.start_y,
+            tick_rate: file.tick_rate,
+            spans,
+            span: 0,
+            tick: 0
+        })
+    }
+
+    /// Total number of ticks in the replay.
+    pub fn tick_count(&self) -> u32 {
+        self.spans.iter().map(|(ticks, _)| ticks).sum()
+    }
+
+    /// Input for the next tick, or `None` once the replay has ended.
+    pub fn next_keys(&mut self) -> Option<Keys> {
+        while let Some((ticks, held)) = self.spans.get(self.span) {
+            if self.tick < *ticks {
+                self.tick += 1;
+                return Some(Keys::holding(held));
+            }
+            self.span += 1;
+            self.tick = 0;
+        }
+        None
+    }
+}
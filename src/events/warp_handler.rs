@@ -0,0 +1,243 @@
+extern crate sdl3;
+
+use std::{fmt, fs, path::{Path, PathBuf}};
+
+use sdl3::{
+    pixels::Color,
+    render::{BlendMode, Canvas},
+    video::Window
+};
+use serde::Deserialize;
+
+use crate::{events::key_handler::Direction, tiles::tile_handler::TileHandler};
+
+/// Default location of the warp definitions, next to the maps they connect.
+pub const WARPS_PATH: &str = "res/maps/warps.toml";
+
+/// Number of frames spent fading out, and again fading back in (~1/3 s at 60 FPS).
+const FADE_FRAMES: u32 = 20;
+
+// ========== FILE FORMAT ==========
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WarpsFile {
+    #[serde(default)]
+    warp: Vec<WarpDefinition>
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WarpDefinition {
+    from: WarpSource,
+    to: WarpDestination,
+    #[serde(default)]
+    fade: bool
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WarpSource {
+    map: String,
+    x: u32,
+    y: u32
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WarpDestination {
+    map: String,
+    x: u32,
+    y: u32,
+    facing: Direction
+}
+
+// ========== RESOLVED WARPS ==========
+
+/// A warp with its map names resolved to indices into `TileHandler::maps`.
+pub struct Warp {
+    pub from_map: usize,
+    pub from_x: u32,
+    pub from_y: u32,
+    pub to_map: usize,
+    pub to_x: u32,
+    pub to_y: u32,
+    pub facing: Direction,
+    pub fade: bool
+}
+
+/// Everything that can go wrong while reading the warp definitions.
+#[derive(Debug)]
+pub enum WarpLoadError {
+    Io { path: PathBuf, message: String },
+    Parse { path: PathBuf, message: String },
+    UnknownMap { path: PathBuf, map: String },
+    OutOfBounds { path: PathBuf, map: String, x: u32, y: u32 }
+}
+
+impl fmt::Display for WarpLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WarpLoadError::Io { path, message } => {
+                write!(f, "{}: failed to read warps: {}", path.display(), message)
+            },
+            WarpLoadError::Parse { path, message } => {
+                write!(f, "{}: invalid warps: {}", path.display(), message)
+            },
+            WarpLoadError::UnknownMap { path, map } => {
+                write!(f, "{}: no map named '{}'", path.display(), map)
+            },
+            WarpLoadError::OutOfBounds { path, map, x, y } => {
+                write!(f, "{}: tile ({}, {}) is outside map '{}'", path.display(), x, y, map)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WarpLoadError {}
+
+/// Progress of a fade transition. The map switches when the screen is fully black.
+struct Transition {
+    warp: usize,    // Index into `WarpHandler::warps`
+    frame: u32      // Frames elapsed, from 0 to 2 * FADE_FRAMES
+}
+
+/// Detects when the player steps onto a warp tile and drives the optional
+/// fade transition between maps.
+pub struct WarpHandler {
+    warps: Vec<Warp>,
+    last_tile: Option<(usize, u32, u32)>,   // Map and tile the player was on last update
+    transition: Option<Transition>
+}
+
+impl WarpHandler {
+    /// Loads warp definitions and checks them against the loaded maps.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the TOML warp file
+    /// * `tile_handler` - Provides the maps warps refer to by name
+    pub fn load(path: impl AsRef<Path>, tile_handler: &TileHandler) -> Result<Self, WarpLoadError> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path).map_err(|e| WarpLoadError::Io {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+        let file: WarpsFile = toml::from_str(&source).map_err(|e| WarpLoadError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+
+        // Resolves a map name and checks the tile lies inside that map
+        let resolve = |map: &str, x: u32, y: u32| -> Result<usize, WarpLoadError> {
+            let index = tile_handler.map_index(map).ok_or_else(|| WarpLoadError::UnknownMap {
+                path: path.to_path_buf(),
+                map: map.to_string()
+            })?;
+            let target = &tile_handler.maps[index];
+            if x >= target.col_len() || y >= target.row_len() {
+                return Err(WarpLoadError::OutOfBounds {
+                    path: path.to_path_buf(),
+                    map: map.to_string(),
+                    x,
+                    y
+                });
+            }
+            Ok(index)
+        };
+
+        let mut warps = Vec::new();
+        for definition in file.warp {
+            warps.push(Warp {
+                from_map: resolve(&definition.from.map, definition.from.x, definition.from.y)?,
+                from_x: definition.from.x,
+                from_y: definition.from.y,
+                to_map: resolve(&definition.to.map, definition.to.x, definition.to.y)?,
+                to_x: definition.to.x,
+                to_y: definition.to.y,
+                facing: definition.to.facing,
+                fade: definition.fade
+            });
+        }
+
+        Ok(Self { warps, last_tile: None, transition: None })
+    }
+
+    /// Whether a fade is in progress. Player movement is frozen meanwhile.
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Cancels any fade and forgets the last tile, e.g. when a new game
+    /// starts or a save is loaded.
+    pub fn reset(&mut self) {
+        self.last_tile = None;
+        self.transition = None;
+    }
+
+    /// Advances warp detection by one frame.
+    ///
+    /// A warp only fires when the player enters its tile, so arriving on a
+    /// tile that is itself a warp does not bounce the player straight back.
+    ///
+    /// # Arguments
+    /// * `active_map` - Index of the map the player is on
+    /// * `tile_x`, `tile_y` - Tile under the centre of the player's hitbox
+    ///
+    /// # Returns
+    /// The warp to apply this frame, if any. With a fade this is returned
+    /// once the screen is fully black.
+    pub fn update(&mut self, active_map: usize, tile_x: u32, tile_y: u32) -> Option<&Warp> {
+        // ===== FADE IN PROGRESS =====
+        if let Some(transition) = &mut self.transition {
+            transition.frame += 1;
+            let warp = transition.warp;
+            if transition.frame >= 2 * FADE_FRAMES {
+                self.transition = None;
+            }
+            else if transition.frame == FADE_FRAMES {
+                return Some(self.arrive(warp));
+            }
+            return None;
+        }
+
+        // ===== WARP DETECTION =====
+        let tile = (active_map, tile_x, tile_y);
+        if self.last_tile == Some(tile) {
+            return None;
+        }
+        self.last_tile = Some(tile);
+
+        let warp = self.warps.iter().position(|warp| {
+            warp.from_map == active_map && warp.from_x == tile_x && warp.from_y == tile_y
+        })?;
+
+        if self.warps[warp].fade {
+            self.transition = Some(Transition { warp, frame: 0 });
+            return None;
+        }
+        Some(self.arrive(warp))
+    }
+
+    /// Records the destination tile as visited and returns the warp.
+    fn arrive(&mut self, warp: usize) -> &Warp {
+        let warp = &self.warps[warp];
+        self.last_tile = Some((warp.to_map, warp.to_x, warp.to_y));
+        warp
+    }
+
+    /// Darkens the screen according to the current fade progress.
+    /// Does nothing when no transition is running.
+    pub fn draw_fade(&self, canvas: &mut Canvas<Window>) {
+        let Some(transition) = &self.transition else {
+            return;
+        };
+
+        // Ramp up to fully opaque at the midpoint, then back down
+        let distance = FADE_FRAMES.abs_diff(transition.frame);
+        let alpha = 255 - (distance * 255 / FADE_FRAMES).min(255);
+
+        canvas.set_blend_mode(BlendMode::Blend);
+        canvas.set_draw_color(Color::RGBA(0, 0, 0, alpha as u8));
+        canvas.fill_rect(None).ok();
+    }
+}
@@ -0,0 +1,123 @@
+use crate::items::item::ItemLibrary;
+
+/// Number of slots in the player's inventory.
+pub const INVENTORY_SLOTS: usize = 20;
+
+/// Some amount of one item, filling one inventory slot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ItemStack {
+    pub item: String,       // Item id, see `ItemLibrary`
+    pub count: u32          // Between 1 and the item's stack size
+}
+
+/// A fixed number of slots, each empty or holding one stack.
+///
+/// Items of the same kind share slots up to their stack size; any more
+/// start a new stack in the first empty slot.
+pub struct Inventory {
+    slots: Vec<Option<ItemStack>>
+}
+
+impl Inventory {
+    /// Creates an empty inventory with `capacity` slots.
+    pub fn new(capacity: usize) -> Self {
+        Self { slots: vec![None; capacity] }
+    }
+
+    /// Every slot, in order.
+    pub fn slots(&self) -> &[Option<ItemStack>] {
+        &self.slots
+    }
+
+    /// Replaces the contents of slot `index`, e.g. when restoring a save.
+    /// Stack sizes are not checked.
+    ///
+    /// # Returns
+    /// `false`, changing nothing, if there is no slot `index`
+    pub fn set(&mut self, index: usize, stack: Option<ItemStack>) -> bool {
+        let Some(slot) = self.slots.get_mut(index) else {
+            return false;
+        };
+        *slot = stack;
+        true
+    }
+
+    /// Adds up to `count` of item `id`, topping up existing stacks before
+    /// filling empty slots.
+    ///
+    /// # Arguments
+    /// * `items` - Provides the item's stack size
+    /// * `id` - Item to add
+    /// * `count` - How many to add
+    ///
+    /// # Returns
+    /// How many did not fit. Unknown items never fit.
+    pub fn add(&mut self, items: &ItemLibrary, id: &str, count: u32) -> u32 {
+        let Some(item) = items.get(id) else {
+            return count;
+        };
+        let mut remaining = count;
+
+        for stack in self.slots.iter_mut().flatten().filter(|stack| stack.item == id) {
+            let added = remaining.min(item.stack_size.saturating_sub(stack.count));
+            stack.count += added;
+            remaining -= added;
+        }
+        for slot in self.slots.iter_mut().filter(|slot| slot.is_none()) {
+            if remaining == 0 {
+                break;
+            }
+            let added = remaining.min(item.stack_size);
+            *slot = Some(ItemStack { item: id.to_string(), count: added });
+            remaining -= added;
+        }
+
+        remaining
+    }
+
+    /// Removes `count` of item `id`, taking from the last stacks first.
+    ///
+    /// # Returns
+    /// `false`, removing nothing, if the inventory holds fewer than `count`
+    pub fn remove(&mut self, id: &str, count: u32) -> bool {
+        if self.count(id) < count {
+            return false;
+        }
+        let mut remaining = count;
+
+        for slot in self.slots.iter_mut().rev() {
+            let Some(stack) = slot.as_mut().filter(|stack| stack.item == id) else {
+                continue;
+            };
+            let removed = remaining.min(stack.count);
+            stack.count -= removed;
+            remaining -= removed;
+            if stack.count == 0 {
+                *slot = None;
+            }
+            if remaining == 0 {
+                break;
+            }
+        }
+        true
+    }
+
+    /// How many of item `id` the inventory holds across all stacks.
+    pub fn count(&self, id: &str) -> u32 {
+        self.slots.iter()
+            .flatten()
+            .filter(|stack| stack.item == id)
+            .map(|stack| stack.count)
+            .sum()
+    }
+
+    /// Whether the inventory holds at least one of item `id`.
+    pub fn contains(&self, id: &str) -> bool {
+        self.count(id) > 0
+    }
+
+    /// Whether every slot is empty.
+    pub fn is_empty(&self) -> bool {
+        self.slots.iter().all(|slot| slot.is_none())
+    }
+}
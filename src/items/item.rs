@@ -0,0 +1,140 @@
+extern crate sdl3;
+
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf}
+};
+
+use sdl3::{
+    rect::Rect,
+    render::Canvas,
+    video::Window
+};
+use serde::Deserialize;
+
+use crate::assets::asset_manager::{AssetManager, TextureHandle};
+
+/// Default location of the item definitions.
+pub const ITEMS_PATH: &str = "res/items/items.toml";
+
+// ========== FILE FORMAT ==========
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ItemsFile {
+    #[serde(default)]
+    item: HashMap<String, ItemDefinition>
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ItemDefinition {
+    name: String,
+    icon: PathBuf,                  // Image relative to the items file
+    #[serde(default = "default_stack_size")]
+    stack_size: u32,
+    #[serde(default)]
+    description: String
+}
+
+fn default_stack_size() -> u32 {
+    1
+}
+
+/// Everything that can go wrong while reading the item definitions.
+#[derive(Debug)]
+pub enum ItemLoadError {
+    Io { path: PathBuf, message: String },
+    Parse { path: PathBuf, message: String },
+    InvalidStackSize { path: PathBuf, item: String }
+}
+
+impl fmt::Display for ItemLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemLoadError::Io { path, message } => {
+                write!(f, "{}: failed to read items: {}", path.display(), message)
+            },
+            ItemLoadError::Parse { path, message } => {
+                write!(f, "{}: invalid items: {}", path.display(), message)
+            },
+            ItemLoadError::InvalidStackSize { path, item } => {
+                write!(f, "{}: item '{}' needs a stack size above 0", path.display(), item)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ItemLoadError {}
+
+// ========== ITEMS ==========
+
+/// A kind of item the player can carry.
+pub struct Item {
+    pub id: String,
+    pub name: String,
+    pub icon: PathBuf,
+    pub icon_texture: Option<TextureHandle>,    // Set by `ItemLibrary::load_textures`
+    pub stack_size: u32,                        // Most of the item one inventory slot holds
+    pub description: String
+}
+
+/// Every item in the game, by id.
+pub struct ItemLibrary {
+    items: HashMap<String, Item>
+}
+
+impl ItemLibrary {
+    /// Loads the item definitions at `path`. Icons are only loaded by
+    /// `load_textures`, so the library also works headless.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ItemLoadError> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path).map_err(|e| ItemLoadError::Io {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+        let file: ItemsFile = toml::from_str(&source).map_err(|e| ItemLoadError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+
+        let mut items = HashMap::new();
+        for (id, definition) in file.item {
+            if definition.stack_size == 0 {
+                return Err(ItemLoadError::InvalidStackSize { path: path.to_path_buf(), item: id });
+            }
+            items.insert(id.clone(), Item {
+                id,
+                name: definition.name,
+                icon: base_dir.join(definition.icon),
+                icon_texture: None,
+                stack_size: definition.stack_size,
+                description: definition.description
+            });
+        }
+
+        Ok(Self { items })
+    }
+
+    /// Loads every item's icon through `assets`.
+    pub fn load_textures(&mut self, assets: &mut AssetManager) {
+        for item in self.items.values_mut() {
+            item.icon_texture = Some(assets.load_texture(&item.icon));
+        }
+    }
+
+    /// The item called `id`.
+    pub fn get(&self, id: &str) -> Option<&Item> {
+        self.items.get(id)
+    }
+
+    /// Draws the icon of item `id` stretched over `dest`. Nothing is drawn
+    /// for unknown items or before `load_textures`.
+    pub fn draw_icon(&self, canvas: &mut Canvas<Window>, assets: &AssetManager, id: &str, dest: Rect) {
+        if let Some(texture) = self.get(id).and_then(|item| item.icon_texture) {
+            canvas.copy(assets.texture(texture), None, dest).ok();
+        }
+    }
+}
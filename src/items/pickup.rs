@@ -0,0 +1,171 @@
+extern crate sdl3;
+
+use std::{fmt, fs, path::{Path, PathBuf}};
+
+use sdl3::{
+    rect::Rect,
+    render::Canvas,
+    video::Window
+};
+use serde::Deserialize;
+
+use crate::{
+    assets::asset_manager::AssetManager,
+    entities::camera::Camera,
+    events::collision_handler::HitBox,
+    items::item::ItemLibrary,
+    tiles::tile_handler::TileHandler,
+    world::World
+};
+
+/// Default location of the item pickups, next to the maps they lie on.
+pub const PICKUPS_PATH: &str = "res/maps/pickups.toml";
+
+// ========== FILE FORMAT ==========
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PickupsFile {
+    #[serde(default)]
+    pickup: Vec<PickupDefinition>
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PickupDefinition {
+    item: String,
+    map: String,
+    x: u32,
+    y: u32,
+    #[serde(default = "default_count")]
+    count: u32
+}
+
+fn default_count() -> u32 {
+    1
+}
+
+/// Everything that can go wrong while reading the item pickups.
+#[derive(Debug)]
+pub enum PickupLoadError {
+    Io { path: PathBuf, message: String },
+    Parse { path: PathBuf, message: String },
+    UnknownItem { path: PathBuf, item: String },
+    UnknownMap { path: PathBuf, map: String },
+    OutOfBounds { path: PathBuf, item: String, x: u32, y: u32 },
+    InvalidCount { path: PathBuf, item: String }
+}
+
+impl fmt::Display for PickupLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PickupLoadError::Io { path, message } => {
+                write!(f, "{}: failed to read pickups: {}", path.display(), message)
+            },
+            PickupLoadError::Parse { path, message } => {
+                write!(f, "{}: invalid pickups: {}", path.display(), message)
+            },
+            PickupLoadError::UnknownItem { path, item } => {
+                write!(f, "{}: pickup of unknown item '{}'", path.display(), item)
+            },
+            PickupLoadError::UnknownMap { path, map } => {
+                write!(f, "{}: pickup placed on unknown map '{}'", path.display(), map)
+            },
+            PickupLoadError::OutOfBounds { path, item, x, y } => {
+                write!(f, "{}: pickup of '{}' at tile ({}, {}) is outside its map", path.display(), item, x, y)
+            },
+            PickupLoadError::InvalidCount { path, item } => {
+                write!(f, "{}: pickup of '{}' needs a count above 0", path.display(), item)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PickupLoadError {}
+
+// ========== PICKUPS ==========
+
+/// Some amount of an item lying on a map until the player walks over it.
+pub struct Pickup {
+    pub item: String,           // Item id, see `ItemLibrary`
+    pub map: usize,             // Index into `TileHandler::maps`
+    pub rect: Rect,             // World rectangle of the tile it lies on
+    pub count: u32,             // How many are left, after any that did not fit
+    pub collected: bool,        // Picked up entirely; kept so indices stay stable
+    pub flag: String            // Story flag set once collected, so saves remember it
+}
+
+impl Pickup {
+    /// The middle half of the tile, so the player has to step onto the
+    /// item rather than brush past it.
+    pub fn hit_box(&self) -> HitBox {
+        let (width, height) = (self.rect.width() / 2, self.rect.height() / 2);
+        HitBox::new(self.rect.x + (width / 2) as i32, self.rect.y + (height / 2) as i32, width, height)
+    }
+}
+
+/// Loads the item pickups and checks them against the loaded items and maps.
+///
+/// # Arguments
+/// * `path` - Path to the TOML pickups file
+/// * `tile_handler` - Provides the maps pickups refer to by name
+/// * `items` - Provides the items pickups refer to by id
+pub fn load_pickups(path: impl AsRef<Path>, tile_handler: &TileHandler, items: &ItemLibrary) -> Result<Vec<Pickup>, PickupLoadError> {
+    let path = path.as_ref();
+    let source = fs::read_to_string(path).map_err(|e| PickupLoadError::Io {
+        path: path.to_path_buf(),
+        message: e.to_string()
+    })?;
+    let file: PickupsFile = toml::from_str(&source).map_err(|e| PickupLoadError::Parse {
+        path: path.to_path_buf(),
+        message: e.to_string()
+    })?;
+    let tile_size = tile_handler.tile_size;
+
+    let mut pickups = Vec::new();
+    for definition in file.pickup {
+        if items.get(&definition.item).is_none() {
+            return Err(PickupLoadError::UnknownItem { path: path.to_path_buf(), item: definition.item });
+        }
+        let map = tile_handler.map_index(&definition.map).ok_or_else(|| PickupLoadError::UnknownMap {
+            path: path.to_path_buf(),
+            map: definition.map.clone()
+        })?;
+        let target = &tile_handler.maps[map];
+        if definition.x >= target.col_len() || definition.y >= target.row_len() {
+            return Err(PickupLoadError::OutOfBounds {
+                path: path.to_path_buf(),
+                item: definition.item,
+                x: definition.x,
+                y: definition.y
+            });
+        }
+        if definition.count == 0 {
+            return Err(PickupLoadError::InvalidCount { path: path.to_path_buf(), item: definition.item });
+        }
+
+        pickups.push(Pickup {
+            item: definition.item,
+            map,
+            rect: Rect::new((definition.x * tile_size) as i32, (definition.y * tile_size) as i32, tile_size, tile_size),
+            count: definition.count,
+            collected: false,
+            flag: format!("picked_up.{}.{}.{}", definition.map, definition.x, definition.y)
+        });
+    }
+
+    Ok(pickups)
+}
+
+/// Draws the icon of every pickup still lying on the active map.
+///
+/// # Arguments
+/// * `canvas` - SDL canvas to draw on
+/// * `assets` - Holds the item icons
+/// * `world` - Pickups to draw and the items they hold
+/// * `camera` - Converts world positions to the screen
+pub fn render_pickups(canvas: &mut Canvas<Window>, assets: &AssetManager, world: &World, camera: &Camera) {
+    for pickup in world.pickups.iter().filter(|pickup| pickup.map == world.active_map && !pickup.collected) {
+        world.items.draw_icon(canvas, assets, &pickup.item, camera.to_screen(pickup.rect));
+    }
+}
@@ -0,0 +1,16 @@
+//! Game state and rendering for the 2D Adventure game.
+//!
+//! The binary in `main.rs` drives everything through an SDL window; tests
+//! and tools can use `world::World` on its own to simulate the game headless.
+
+pub mod assets;
+pub mod entities;
+pub mod events;
+pub mod items;
+pub mod save;
+pub mod settings;
+pub mod states;
+pub mod tiles;
+pub mod timing;
+pub mod ui;
+pub mod world;
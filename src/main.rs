@@ -1,52 +1,267 @@
 extern crate sdl3;
 
 use sdl3::{
-    event::Event, rect::Rect,
+    event::Event,
+    render::Canvas,
+    video::{Window, WindowPos}
 };
-use std::time::{Instant};
+use std::path::{Path, PathBuf};
 
-mod entities;
-use entities::{
-    player::Player,
-    camera::Camera
+use _2d_adventure::{
+    assets::{
+        asset_manager::AssetManager,
+        hot_reload::ResourceWatcher,
+        image_loader
+    },
+    entities::{
+        character_sprite::CharacterSprite,
+        entity::EntitySprites,
+        player::Player,
+        camera::Camera
+    },
+    events::{
+        gamepad_handler::GamepadHandler,
+        key_bindings::{KeyBindings, KEY_BINDINGS_PATH},
+        key_handler::Keys,
+        key_handler::key_handler,
+        replay::{Replay, ReplayRecorder}
+    },
+    save::{SaveSlots, SAVE_APPLICATION, SAVE_ORGANIZATION, SAVE_SLOTS},
+    settings::{Settings, SETTINGS_PATH},
+    states::{
+        gameplay::GameplayState,
+        state::{Frame, Game, StartPosition, State, StateStack},
+        title::TitleState
+    },
+    timing::FixedTimestep,
+    ui::font::{BitmapFont, Font, TextStyle, TtfFont, DEFAULT_FONT_PATH, TITLE_FONT_PATH},
+    world::World
 };
 
-mod events;
-use events::{
-    key_handler::Keys,
-    key_handler::key_handler
-};
+/// Command line options.
+///
+/// * `--record <file>` - Save this run's input as a replay when the window closes
+/// * `--replay <file>` - Play a replay back instead of reading live input
+/// * `--headless` - With `--replay`, simulate without a window and print where the player ended up
+/// * `--tick-rate <hz>` - Game updates per second (default 60); replays use their own
+/// * `--vsync` - Wait for the display's refresh instead of sleeping between frames
+/// * `--dev` - Watch `res/` and reload edited images, sprite sheets and maps while running,
+///   and show the player's position on screen
+/// * `--slot <n>` - Save slot the title screen continues from and the game saves to (1 to 3, default 1)
+struct Options {
+    record: Option<PathBuf>,
+    replay: Option<PathBuf>,
+    headless: bool,
+    tick_rate: Option<u32>,
+    vsync: bool,
+    dev: bool,
+    slot: u32
+}
 
-mod tiles;
-use tiles::{
-    tile_handler::TileHandler
-};
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self {
+            record: None,
+            replay: None,
+            headless: false,
+            tick_rate: None,
+            vsync: false,
+            dev: false,
+            slot: 1
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--record" => {
+                    options.record = Some(args.next().ok_or("--record needs a file")?.into());
+                },
+                "--replay" => {
+                    options.replay = Some(args.next().ok_or("--replay needs a file")?.into());
+                },
+                "--headless" => options.headless = true,
+                "--tick-rate" => {
+                    let rate = args.next().ok_or("--tick-rate needs a number")?;
+                    options.tick_rate = match rate.parse() {
+                        Ok(rate) if rate > 0 => Some(rate),
+                        _ => return Err(format!("Invalid tick rate '{}'", rate))
+                    };
+                },
+                "--vsync" => options.vsync = true,
+                "--dev" => options.dev = true,
+                "--slot" => {
+                    let slot = args.next().ok_or("--slot needs a number")?;
+                    options.slot = match slot.parse() {
+                        Ok(slot) if (1..=SAVE_SLOTS).contains(&slot) => slot,
+                        _ => return Err(format!("Invalid save slot '{}', expected 1 to {}", slot, SAVE_SLOTS))
+                    };
+                },
+                _ => return Err(format!("Unknown argument '{}'", arg))
+            }
+        }
+        if options.headless && options.replay.is_none() {
+            return Err("--headless needs --replay <file>".to_string());
+        }
+        Ok(options)
+    }
+}
 
-use crate::events::collision_handler::{self, CollisionDetector};
+/// Reloads whatever was loaded from `path` in place, printing the outcome.
+/// Errors keep the old version on screen rather than stopping the game.
+fn reload_resource<'s>(
+    path: &Path,
+    assets: &mut AssetManager,
+    world: &mut World,
+    sprites: impl Iterator<Item = &'s mut CharacterSprite>
+) {
+    let result = if image_loader::is_image(path) {
+        assets.reload_texture(path).map_err(|e| e.to_string())
+    } else if path.parent() == Some(Path::new("res/maps")) {
+        world.tile_handler.reload_map(path)
+    } else {
+        // Several characters may share one sheet
+        let mut result = Ok(false);
+        for sprite in sprites {
+            match sprite.reload(path, assets) {
+                Ok(reloaded) => result = result.map(|any| any || reloaded),
+                Err(error) => {
+                    result = Err(error.to_string());
+                    break;
+                }
+            }
+        }
+        result
+    };
+
+    match result {
+        Ok(true) => println!("Reloaded {}", path.display()),
+        Ok(false) => {},
+        Err(error) => println!("Reload failed: {}", error)
+    }
+}
+
+/// Switches fullscreen on or off and sizes the window to match `settings`.
+/// The window is `window_scale` times `base_width` x `base_height` outside
+/// fullscreen. Failures are printed and leave the window as it was.
+fn apply_settings(canvas: &mut Canvas<Window>, settings: &Settings, base_width: u32, base_height: u32) {
+    let window = canvas.window_mut();
+    if let Err(error) = window.set_fullscreen(settings.fullscreen) {
+        println!("Could not change fullscreen: {}", error);
+    }
+    if !settings.fullscreen {
+        let (width, height) = (base_width * settings.window_scale, base_height * settings.window_scale);
+        match window.set_size(width, height) {
+            Ok(()) => {
+                window.set_position(WindowPos::Centered, WindowPos::Centered);
+            },
+            Err(error) => println!("Could not resize the window: {}", error)
+        }
+    }
+}
 
 /// Entry point for the 2D Adventure game.
 /// 
-/// This function initializes SDL3, creates the game window, and runs the main game loop
-/// at a fixed 60 FPS using delta time accumulation for frame-independent timing.
+/// This function initializes SDL3, creates the game window, and runs the main game loop:
+/// fixed-rate updates (60 per second by default) with interpolated rendering in between.
 fn main() -> Result< (), String> {
+    let options = Options::parse(std::env::args().skip(1))?;
+
     // ========== SCREEN CONFIGURATION ==========
-    // Define the base tile size and scaling factor for pixel art rendering
+    // The world is laid out in pixel art tiles drawn at a fixed scale, so
+    // positions in saves and replays do not depend on the window size.
+    // The window scale setting only stretches the finished frame
     const ORIGINAL_TILE_SIZE: u32 = 16; // Base 16x16 pixel tiles
-    const SCALE: u32 = 3;                // Scale up by 3x for better visibility
+    const WORLD_SCALE: u32 = 3;          // Tiles are laid out 3x as large
 
-    // Calculate scaled tile size and screen dimensions
-    let tile_size = ORIGINAL_TILE_SIZE * SCALE; // Results in 48x48 pixel tiles
+    // Calculate scaled tile size and the size the game renders at
+    let tile_size = ORIGINAL_TILE_SIZE * WORLD_SCALE;  // Results in 48x48 pixel tiles
     let max_screen_col = 16;                     // 16 tiles horizontally
     let max_screen_row = 12;                     // 12 tiles vertically
     let screen_width = tile_size * max_screen_col;   // 768 pixels wide
     let screen_height = tile_size * max_screen_row;  // 576 pixels tall
+    let base_width = ORIGINAL_TILE_SIZE * max_screen_col;    // Window size at scale 1
+    let base_height = ORIGINAL_TILE_SIZE * max_screen_row;
+
+    // ========== WORLD CONFIGURATION ==========
+    // The map the game starts on; warps switch between maps from here
+    const START_MAP: &str = "002_worldmap";
+    const PLAYER_SPEED: i32 = 3;            // Pixels per tick, doubled while running
+    const DEFAULT_TICK_RATE: u32 = 60;      // Fixed updates per second
+
+    // A replay brings its own start position and tick rate
+    let mut replay = match &options.replay {
+        Some(path) => Some(Replay::load(path).map_err(|e| e.to_string())?),
+        None => None
+    };
+    let (start_map, start_x, start_y, tick_rate) = match &replay {
+        Some(replay) => (replay.map.clone(), replay.start_x, replay.start_y, replay.tick_rate),
+        // Start inside the house on the world map
+        None => (START_MAP.to_string(), 460, 364, options.tick_rate.unwrap_or(DEFAULT_TICK_RATE))
+    };
+
+    // Maps, warps and the player, updated without touching the renderer
+    let player = Player::new(start_x, start_y, PLAYER_SPEED, tile_size);
+    let world = World::new(tile_size, &start_map, player)?;
+    let start = StartPosition { map: start_map.clone(), x: start_x, y: start_y, player_speed: PLAYER_SPEED };
+    let mut game = Game::new(world, start);
+
+    // ========== HEADLESS PLAYBACK ==========
+    // Run the whole replay as fast as possible and report where it ended.
+    // Replays start in gameplay, the same as when they were recorded
+    if options.headless {
+        let mut replay = replay.take().ok_or("--headless needs --replay <file>")?;
+        let ticks = replay.tick_count();
+        let mut stack = StateStack::new(Box::new(GameplayState));
+        while let Some(keys) = replay.next_keys() {
+            stack.update(&mut game, &keys);
+            if !stack.is_running() {
+                break;
+            }
+        }
+        let world = &game.world;
+        println!(
+            "Replay finished after {} ticks on map '{}' at X: {}, Y: {}",
+            ticks, world.map().name, world.player.rect.x, world.player.rect.y
+        );
+        return Ok(());
+    }
+
+    // ========== SAVE GAME ==========
+    // Recordings skip the title screen and never touch the save slot so
+    // they play back the same whatever has been saved
+    let recording_or_replaying = options.record.is_some() || replay.is_some();
+    if !recording_or_replaying {
+        match sdl3::filesystem::get_pref_path(SAVE_ORGANIZATION, SAVE_APPLICATION) {
+            Ok(dir) => game.saves = Some(SaveSlots::new(dir)),
+            Err(error) => println!("Saving is disabled, no user data directory: {}", error)
+        }
+    }
+    game.slot = options.slot;
+    let initial: Box<dyn State> = if recording_or_replaying {
+        Box::new(GameplayState)
+    } else {
+        Box::new(TitleState::new(&game))
+    };
+    let mut stack = StateStack::new(initial);
+
+    // ========== SETTINGS ==========
+    // Window size, fullscreen and volume from config/settings.toml, and
+    // which keys trigger which actions from config/key_bindings.toml.
+    // Both are changed from the options menu
+    game.settings = Settings::load_or_default(SETTINGS_PATH).map_err(|e| e.to_string())?;
+    game.key_bindings = KeyBindings::load_or_default(KEY_BINDINGS_PATH)
+        .map_err(|e| e.to_string())?;
+
+    // Records the input of every tick when `--record` is given
+    let mut recorder = options.record.as_ref().map(|_| {
+        ReplayRecorder::new(&start_map, start_x, start_y, tick_rate)
+    });
 
     // ========== SDL3 INITIALIZATION ==========
     let sdl_context = sdl3::init().unwrap();
     let video_subsystem = sdl_context.video().unwrap();
     
     // Create centered game window
-    let window = video_subsystem.window("2D Adventure", screen_width, screen_height)
+    let window_scale = game.settings.window_scale;
+    let window = video_subsystem.window("2D Adventure", base_width * window_scale, base_height * window_scale)
         .position_centered()
         .build()
         .expect("Failed to build window");
@@ -54,119 +269,202 @@ fn main() -> Result< (), String> {
     // ========== RENDERING SETUP ==========
     // Convert window to canvas for 2D rendering
     let mut canvas = window.into_canvas();
+    if options.vsync && !unsafe { sdl3_sys::render::SDL_SetRenderVSync(canvas.raw(), 1) } {
+        println!("Vsync is not supported here: {}", sdl3::get_error());
+    }
+    // Everything is drawn at the same size and stretched to fit the window,
+    // with black bars if the window has another shape
+    canvas.set_logical_size(screen_width, screen_height, sdl3_sys::render::SDL_LOGICAL_PRESENTATION_LETTERBOX)
+        .map_err(|e| e.to_string())?;
+    let mut applied_settings = game.settings;
+    apply_settings(&mut canvas, &applied_settings, base_width, base_height);
     let texture_creator = canvas.texture_creator();
-    let tile_handler = TileHandler::new(tile_size, &texture_creator);
-    let collision_handler = CollisionDetector::new(&tile_handler, tile_size);
     canvas.clear();
 
+    // ========== ASSETS ==========
+    // Every texture is loaded once here; tiles and sprites keep handles to them
+    let mut assets = AssetManager::new(&texture_creator)?;
+    game.world.tile_handler.load_textures(&mut assets);
+    game.world.items.load_textures(&mut assets);
+    let mut entity_sprites = EntitySprites::load(&game.world, &mut assets)?;
+
+    // Dialogue and menus are drawn in a pixel font; titles and the
+    // development overlay use TrueType
+    let ttf_context = sdl3::ttf::init().map_err(|e| e.to_string())?;
+    let mut text_font = Font::Bitmap(BitmapFont::load(DEFAULT_FONT_PATH, &mut assets).map_err(|e| e.to_string())?);
+    let mut title_font = Font::Ttf(TtfFont::load(&ttf_context, TITLE_FONT_PATH, 48.0, &texture_creator).map_err(|e| e.to_string())?);
+    let mut debug_font = if options.dev {
+        Some(Font::Ttf(TtfFont::load(&ttf_context, TITLE_FONT_PATH, 14.0, &texture_creator).map_err(|e| e.to_string())?))
+    } else {
+        None
+    };
+
+    // Missing images are drawn as a placeholder rather than stopping the game
+    for failure in assets.failures() {
+        println!("Asset failed to load: {}", failure);
+    }
+
+    // In development mode edits under res/ show up without a restart
+    let mut resource_watcher = options.dev.then(|| ResourceWatcher::new("res"));
+
     // ========== INPUT STATE ==========
     // Event pump processes SDL events each frame
     let mut event_pump = sdl_context.event_pump().unwrap();
-    
-    // Tracks the state of WASD keys (pressed/released)
-    let mut keys = Keys{
-        w: false,
-        a: false,
-        s: false,
-        d: false
-    };
 
-    // ========== PLAYER INITIALIZATION ==========
-    // Create player at position (100, 100) with speed 3 pixels per frame
+    // Tracks which input actions are held down; one-shot presses are
+    // derived from it each tick by `game.presses`
+    let mut keys = Keys::new();
+
+    // Opens gamepads as they are connected and feeds them into `keys`
+    let mut gamepad_handler = GamepadHandler::new(sdl_context.gamepad().unwrap());
+
+    // The camera follows the player, scrolling once they leave a small box
+    // around the screen centre, and stops at the edges of the map
     let mut camera: Camera = Camera::new(
-        -100,
-        -100,
+        0,
+        0,
         screen_width, 
-        screen_height, 
-        3
+        screen_height
     );
-    let mut player: Player = Player::new(
-        (screen_width/2 - tile_size/2) as i32 , 
-        (screen_height/2 - tile_size/2) as i32 ,
-         3, tile_size, &texture_creator);
+    camera.deadzone = Some((tile_size * 2, tile_size * 2));
+    camera.smoothing = 0.2;
+    let (map_width, map_height) = game.world.map_size();
+    camera.snap_to(game.world.player.rect, map_width, map_height);
 
     // ========== FRAME TIMING SETUP ==========
-    // Fixed timestep game loop: run at exactly 60 FPS
-    const FPS: u128 = 60;
-    let draw_interval = 1_000_000_000u128 / FPS; // Nanoseconds per frame (~16.67ms)
-    
-    let mut last_time = Instant::now();  // Track previous frame time
-    let mut delta = 0;                   // Accumulated time since last update
-    let mut sprite_counter = 0;          // Counter for animation frame switching
+    // Fixed timestep game loop: update at exactly `tick_rate` per second,
+    // render once per loop and sleep (or wait for vsync) in between
+    const MAX_UPDATES_PER_FRAME: u32 = 5;   // Catch-up cap after a stall
+    let mut timestep = FixedTimestep::new(tick_rate, MAX_UPDATES_PER_FRAME);
 
     // ========== MAIN GAME LOOP ==========
     // This loop continues until the user closes the window
     'running: loop {
-        // Only update/render when enough time has accumulated for a full frame
-        if delta >= draw_interval {
-            // ===== EVENT PROCESSING =====
-            // Poll all pending events and handle them
-            for event in event_pump.poll_iter() {
-                match event {
-                    Event::Quit {..} => {
-                        break 'running; // Exit game on window close
-                    }
-                    _ => {
-                        // Delegate key events to the key handler
-                        key_handler(event, &mut keys);
+        // ===== EVENT PROCESSING =====
+        // Poll all pending events and handle them
+        for event in event_pump.poll_iter() {
+            match event {
+                Event::Quit {..} => {
+                    break 'running; // Exit game on window close
+                }
+                _ => {
+                    // Delegate gamepad and key events to their handlers
+                    // The raw key is kept too so it can be bound in the options
+                    if let Event::KeyDown { keycode: Some(keycode), repeat: false, .. } = event {
+                        game.pressed_key = Some(keycode);
                     }
+                    gamepad_handler.handle_event(&event, &game.key_bindings, &mut keys);
+                    key_handler(event, &game.key_bindings, &mut keys);
                 }
             }
+        }
 
-            // ===== UPDATE PHASE =====
-            // Update player position based on current key states\
-            camera.update(&mut keys, player.on_collision);
-            player.on_collision = false;
-            player.update(
-                &mut keys, 
-                &tile_handler.maps[1], 
-                &collision_handler, 
-                &tile_handler,
-                &camera
-            );
+        // ===== HOT RELOAD =====
+        if let Some(watcher) = &mut resource_watcher {
+            for path in watcher.poll() {
+                reload_resource(&path, &mut assets, &mut game.world, entity_sprites.iter_mut());
+            }
+        }
 
-            // ===== ANIMATION TIMING =====
-            // Switch animation frame every 12 game frames (~200ms at 60 FPS)
-            // This creates a visible walking animation without being too fast
-            player.change_sprite = if sprite_counter >= 12 {
-                sprite_counter = 0;
-                true  // Signal to change to next animation frame
-            } else {
-                sprite_counter += 1;
-                false // Keep current animation frame
+        // ===== FIXED UPDATES =====
+        // Run every tick that has come due since the last frame
+        for _ in 0..timestep.advance() {
+            // ===== REPLAY =====
+            // While a replay is playing its recorded input replaces the live one;
+            // once it ends control goes back to the player
+            let replay_keys = match replay.as_mut().map(|replay| replay.next_keys()) {
+                Some(None) => {
+                    let world = &game.world;
+                    println!(
+                        "Replay finished on map '{}' at X: {}, Y: {}",
+                        world.map().name, world.player.rect.x, world.player.rect.y
+                    );
+                    replay = None;
+                    None
+                },
+                next => next.flatten()
             };
+            let input = replay_keys.as_ref().unwrap_or(&keys);
+            if let Some(recorder) = &mut recorder {
+                recorder.record(input);
+            }
+
+            // ===== UPDATE PHASE =====
+            // The state on top handles the input; the ones below stay frozen
+            stack.update(&mut game, input);
+            if !stack.is_running() {
+                break 'running;
+            }
+
+            // ===== CAMERA =====
+            // Jump straight to the player after a warp or a load, otherwise follow them
+            let world = &game.world;
+            let (map_width, map_height) = world.map_size();
+            if game.teleported {
+                camera.snap_to(world.player.rect, map_width, map_height);
+            }
+            camera.update(world.player.rect, map_width, map_height);
+
+            // ===== ANIMATION =====
+            // Frame timing comes from the clips in each sprite sheet
+            entity_sprites.update(world, timestep.tick());
+        }
+
+        // ===== SETTINGS =====
+        // Resize the window once the options menu changes it
+        if game.settings != applied_settings {
+            applied_settings = game.settings;
+            apply_settings(&mut canvas, &applied_settings, base_width, base_height);
+        }
 
-            // ===== RENDER PHASE =====
-            camera.draw_camera(
-                tile_handler.maps[1].row_len(),
-                tile_handler.maps[1].col_len(), 
-                max_screen_row,
-                max_screen_col,
-                &tile_handler, 
-                &mut canvas, 
-                &tile_handler.maps[1]
+        // ===== RENDER PHASE =====
+        // Draw between the previous and the current tick so movement stays
+        // smooth when frames and ticks do not line up
+        let alpha = timestep.alpha();
+        let view = camera.interpolated(alpha);
+        let mut frame = Frame {
+            canvas: &mut canvas,
+            assets: &mut assets,
+            text_font: &mut text_font,
+            title_font: &mut title_font,
+            entity_sprites: &entity_sprites,
+            camera: &view,
+            alpha,
+            screen_width,
+            screen_height
+        };
+        stack.render(&game, &mut frame);
+        if let Some(font) = &mut debug_font {
+            // Where the player stands, in place of printing it every tick
+            let world = &game.world;
+            let position = format!(
+                "{}\nX: {}, Y: {}",
+                world.map().name, world.player.rect.x, world.player.rect.y
             );
-            // tile_handler.draw_map( max_screen_row, &mut canvas, &tile_handler.maps[0]);
-                                                   // Clear previous frame
-            player.render(&mut canvas, &keys);      // Draw player sprite
-            canvas.draw_rect(Rect::new(player.rect.x + 8, player.rect.y + 16, 32, 32)).ok();
-            canvas.present();                       // Display rendered frame
-
-            // ===== FRAME TIME MANAGEMENT =====
-            // Subtract one frame's worth of time from delta
-            // If multiple frames worth of time accumulated, the loop will
-            // run multiple times to catch up (fixed timestep)
-            delta -= draw_interval;
+            font.draw(&mut canvas, &mut assets, &position, 8, 8, &TextStyle::default());
         }
-        else {
-            // ===== DELTA TIME ACCUMULATION =====
-            // Not enough time has passed for a frame update yet
-            // Accumulate the elapsed time since last measurement
-            let current_time = Instant::now();
-            delta += current_time.duration_since(last_time).as_nanos();
-            last_time = current_time;
+        canvas.present();                       // Display rendered frame
+
+        // ===== FRAME PACING =====
+        // With vsync `present` already waits for the display; otherwise
+        // sleep until the next tick instead of spinning on the clock
+        if !options.vsync {
+            timestep.sleep_until_next_tick();
         }
     }
 
+    // ========== SAVE ON QUIT ==========
+    // Only while a game is being played, not from the title screen
+    if stack.saves_on_quit() && game.saves.is_some() {
+        let path = game.save()?;
+        println!("Saved to {}", path.display());
+    }
+
+    // ========== SAVE RECORDING ==========
+    if let (Some(recorder), Some(path)) = (&recorder, &options.record) {
+        recorder.save(path).map_err(|e| e.to_string())?;
+        println!("Replay saved to {}", path.display());
+    }
+
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,224 @@
+use std::{
+    collections::BTreeSet,
+    fmt, fs,
+    path::PathBuf
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    events::key_handler::Direction,
+    items::inventory::{Inventory, ItemStack, INVENTORY_SLOTS},
+    world::World
+};
+
+/// Version written to new save files. Files with any other version are rejected.
+const SAVE_VERSION: u32 = 1;
+
+/// Number of save slots, numbered from 1.
+pub const SAVE_SLOTS: u32 = 3;
+
+// Names the user data directory is derived from, see `sdl3::filesystem::get_pref_path`
+pub const SAVE_ORGANIZATION: &str = "2d_adventure";
+pub const SAVE_APPLICATION: &str = "2D Adventure";
+
+// ========== FILE FORMAT ==========
+
+/// Just the version, read first so a save from another version is reported
+/// as such rather than as a parse error.
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32
+}
+
+/// On-disk layout of a save. The map is stored by name rather than by its
+/// index in `TileHandler::maps`, so adding maps does not break old saves.
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct SaveFile {
+    version: u32,
+    map: String,
+    x: i32,                     // Player's world position
+    y: i32,
+    facing: Direction,
+    #[serde(default)]
+    flags: BTreeSet<String>,
+    #[serde(default)]
+    inventory: Vec<SavedStack>
+}
+
+/// The stack in inventory slot `slot`. Empty slots are left out.
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct SavedStack {
+    slot: usize,
+    item: String,
+    count: u32
+}
+
+/// Everything that can go wrong while reading or writing a save.
+#[derive(Debug)]
+pub enum SaveError {
+    Io { path: PathBuf, message: String },
+    Parse { path: PathBuf, message: String },
+    UnsupportedVersion { path: PathBuf, version: u32 },
+    UnknownMap { path: PathBuf, map: String },
+    OutOfBounds { path: PathBuf, x: i32, y: i32 },
+    UnknownItem { path: PathBuf, item: String },
+    InvalidStack { path: PathBuf, slot: usize }
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io { path, message } => {
+                write!(f, "{}: failed to access save: {}", path.display(), message)
+            },
+            SaveError::Parse { path, message } => {
+                write!(f, "{}: corrupted save: {}", path.display(), message)
+            },
+            SaveError::UnsupportedVersion { path, version } => {
+                write!(f, "{}: save version {} is not supported (expected {})", path.display(), version, SAVE_VERSION)
+            },
+            SaveError::UnknownMap { path, map } => {
+                write!(f, "{}: save is on unknown map '{}'", path.display(), map)
+            },
+            SaveError::OutOfBounds { path, x, y } => {
+                write!(f, "{}: saved position ({}, {}) is outside the map", path.display(), x, y)
+            },
+            SaveError::UnknownItem { path, item } => {
+                write!(f, "{}: save holds unknown item '{}'", path.display(), item)
+            },
+            SaveError::InvalidStack { path, slot } => {
+                write!(f, "{}: inventory slot {} is invalid or repeated", path.display(), slot)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+// ========== SAVE SLOTS ==========
+
+/// Numbered save files in one directory, normally the user data directory
+/// from `sdl3::filesystem::get_pref_path`.
+///
+/// A save holds the active map, the player's position and facing, the
+/// inventory and the story flags. Everything else (such as NPC positions)
+/// is left as it was before loading.
+pub struct SaveSlots {
+    dir: PathBuf
+}
+
+impl SaveSlots {
+    /// Save slots stored in `dir`. Nothing is created until the first save.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// File slot `slot` is stored in.
+    pub fn path(&self, slot: u32) -> PathBuf {
+        self.dir.join(format!("slot_{}.toml", slot))
+    }
+
+    /// Whether slot `slot` holds a save.
+    pub fn exists(&self, slot: u32) -> bool {
+        self.path(slot).is_file()
+    }
+
+    /// Writes `world` to slot `slot`, replacing any save in it.
+    ///
+    /// The file is written next to the slot and then renamed over it, so
+    /// a crash while saving leaves the previous save intact.
+    pub fn save(&self, slot: u32, world: &World) -> Result<(), SaveError> {
+        let path = self.path(slot);
+        let io_error = |e: std::io::Error| SaveError::Io {
+            path: path.clone(),
+            message: e.to_string()
+        };
+
+        let file = SaveFile {
+            version: SAVE_VERSION,
+            map: world.map().name.clone(),
+            x: world.player.rect.x,
+            y: world.player.rect.y,
+            facing: world.player.direction,
+            flags: world.flags.clone(),
+            inventory: world.player.inventory.slots().iter()
+                .enumerate()
+                .filter_map(|(slot, stack)| {
+                    let stack = stack.as_ref()?;
+                    Some(SavedStack { slot, item: stack.item.clone(), count: stack.count })
+                })
+                .collect()
+        };
+        let source = toml::to_string_pretty(&file).map_err(|e| SaveError::Parse {
+            path: path.clone(),
+            message: e.to_string()
+        })?;
+
+        fs::create_dir_all(&self.dir).map_err(io_error)?;
+        let temporary = path.with_extension("toml.tmp");
+        fs::write(&temporary, source).map_err(io_error)?;
+        fs::rename(&temporary, &path).map_err(io_error)
+    }
+
+    /// Restores the save in slot `slot` into `world`.
+    ///
+    /// The whole save is checked against the loaded maps and items first;
+    /// on error `world` is left untouched.
+    pub fn load(&self, slot: u32, world: &mut World) -> Result<(), SaveError> {
+        let path = self.path(slot);
+        let source = fs::read_to_string(&path).map_err(|e| SaveError::Io {
+            path: path.clone(),
+            message: e.to_string()
+        })?;
+        let parse_error = |e: toml::de::Error| SaveError::Parse {
+            path: path.clone(),
+            message: e.to_string()
+        };
+        let header: SaveHeader = toml::from_str(&source).map_err(parse_error)?;
+        if header.version != SAVE_VERSION {
+            return Err(SaveError::UnsupportedVersion { path, version: header.version });
+        }
+        let file: SaveFile = toml::from_str(&source).map_err(parse_error)?;
+
+        // ===== VALIDATION =====
+        let map = world.tile_handler.map_index(&file.map)
+            .ok_or_else(|| SaveError::UnknownMap { path: path.clone(), map: file.map.clone() })?;
+        let tile_size = world.tile_size as i32;
+        let (map_width, map_height) = {
+            let map = &world.tile_handler.maps[map];
+            (map.col_len() as i32 * tile_size, map.row_len() as i32 * tile_size)
+        };
+        if file.x < 0 || file.y < 0 || file.x + tile_size > map_width || file.y + tile_size > map_height {
+            return Err(SaveError::OutOfBounds { path, x: file.x, y: file.y });
+        }
+
+        let mut inventory = Inventory::new(INVENTORY_SLOTS);
+        for stack in file.inventory {
+            let item = world.items.get(&stack.item)
+                .ok_or_else(|| SaveError::UnknownItem { path: path.clone(), item: stack.item.clone() })?;
+            // The slot must exist and not be listed twice
+            let free = inventory.slots().get(stack.slot).is_some_and(|slot| slot.is_none());
+            if !free || stack.count == 0 || stack.count > item.stack_size {
+                return Err(SaveError::InvalidStack { path, slot: stack.slot });
+            }
+            inventory.set(stack.slot, Some(ItemStack { item: stack.item, count: stack.count }));
+        }
+
+        // ===== RESTORE =====
+        world.active_map = map;
+        world.player.rect.x = file.x;
+        world.player.rect.y = file.y;
+        world.player.previous_rect = world.player.rect;
+        world.player.direction = file.facing;
+        world.player.inventory = inventory;
+        for pickup in &mut world.pickups {
+            pickup.collected = file.flags.contains(&pickup.flag);
+        }
+        world.flags = file.flags;
+        world.warp_handler.reset();
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf}
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Default location of the settings file, relative to the working directory.
+pub const SETTINGS_PATH: &str = "config/settings.toml";
+
+/// Largest window scale offered. The window is this many times the size of
+/// the original pixel art.
+pub const MAX_WINDOW_SCALE: u32 = 4;
+
+/// Volume is a percentage, changed in steps of this much.
+pub const VOLUME_STEP: u32 = 10;
+
+/// Everything that can go wrong while reading or writing settings.
+#[derive(Debug)]
+pub enum SettingsError {
+    Io { path: PathBuf, message: String },
+    Parse { path: PathBuf, message: String },
+    InvalidWindowScale { path: PathBuf, scale: u32 },
+    InvalidVolume { path: PathBuf, volume: u32 }
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsError::Io { path, message } => {
+                write!(f, "{}: failed to access settings: {}", path.display(), message)
+            },
+            SettingsError::Parse { path, message } => {
+                write!(f, "{}: invalid settings: {}", path.display(), message)
+            },
+            SettingsError::InvalidWindowScale { path, scale } => {
+                write!(f, "{}: window scale {} is not between 1 and {}", path.display(), scale, MAX_WINDOW_SCALE)
+            },
+            SettingsError::InvalidVolume { path, volume } => {
+                write!(f, "{}: volume {} is above 100", path.display(), volume)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+/// Display and sound settings changed from the options menu. Key bindings
+/// are kept in their own file, see `KeyBindings`.
+///
+/// Settings missing from the file keep their defaults.
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub window_scale: u32,      // Window size as a multiple of the original pixel art
+    pub fullscreen: bool,
+    pub volume: u32             // Percent; nothing plays sound yet
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self { window_scale: 3, fullscreen: false, volume: 100 }
+    }
+}
+
+impl Settings {
+    /// Loads settings from `path`, or writes the defaults there if the file
+    /// does not exist yet.
+    pub fn load_or_default(path: impl AsRef<Path>) -> Result<Self, SettingsError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            let settings = Self::default();
+            settings.save(path)?;
+            return Ok(settings);
+        }
+        Self::load(path)
+    }
+
+    /// Loads settings from `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SettingsError> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path).map_err(|e| SettingsError::Io {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+        let settings: Settings = toml::from_str(&source).map_err(|e| SettingsError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+
+        if !(1..=MAX_WINDOW_SCALE).contains(&settings.window_scale) {
+            return Err(SettingsError::InvalidWindowScale { path: path.to_path_buf(), scale: settings.window_scale });
+        }
+        if settings.volume > 100 {
+            return Err(SettingsError::InvalidVolume { path: path.to_path_buf(), volume: settings.volume });
+        }
+        Ok(settings)
+    }
+
+    /// Writes the settings to `path`, creating its directory if needed.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SettingsError> {
+        let path = path.as_ref();
+        let io_error = |e: std::io::Error| SettingsError::Io {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        };
+
+        let source = toml::to_string_pretty(self).map_err(|e| SettingsError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(io_error)?;
+        }
+        fs::write(path, source).map_err(io_error)
+    }
+}
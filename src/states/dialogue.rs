@@ -0,0 +1,38 @@
+use crate::{
+    events::key_handler::{Action, Keys},
+    states::state::{Frame, Game, State, Transition},
+    ui::dialogue::Dialogue
+};
+
+/// A dialogue box over the frozen world. Closes once its last page is
+/// confirmed, raising the dialogue's story flag if it has one.
+pub struct DialogueState {
+    dialogue: Dialogue
+}
+
+impl DialogueState {
+    pub fn new(dialogue: Dialogue) -> Self {
+        Self { dialogue }
+    }
+}
+
+impl State for DialogueState {
+    fn update(&mut self, game: &mut Game, _input: &Keys) -> Transition {
+        self.dialogue.update(game.presses.was_pressed(Action::Interact));
+        if self.dialogue.is_finished() {
+            if let Some(flag) = self.dialogue.sets_flag() {
+                game.world.set_flag(flag);
+            }
+            return Transition::Pop;
+        }
+        Transition::Stay
+    }
+
+    fn render(&self, _game: &Game, frame: &mut Frame) {
+        self.dialogue.draw(frame.canvas, frame.assets, frame.text_font, frame.screen_width, frame.screen_height);
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
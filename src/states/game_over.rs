@@ -0,0 +1,45 @@
+extern crate sdl3;
+
+use sdl3::pixels::Color;
+
+use crate::{
+    events::key_handler::{Action, Keys},
+    states::{
+        state::{Frame, Game, State, Transition},
+        title::TitleState
+    },
+    ui::font::{Align, TextStyle}
+};
+
+/// Shown once the game has ended. Interact goes back to the title screen.
+#[derive(Default)]
+pub struct GameOverState;
+
+impl GameOverState {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl State for GameOverState {
+    fn update(&mut self, game: &mut Game, _input: &Keys) -> Transition {
+        if game.presses.was_pressed(Action::Interact) || game.presses.was_pressed(Action::Menu) {
+            return Transition::Reset(Box::new(TitleState::new(game)));
+        }
+        Transition::Stay
+    }
+
+    fn render(&self, _game: &Game, frame: &mut Frame) {
+        let center_x = (frame.screen_width / 2) as i32;
+        frame.canvas.set_draw_color(Color::RGB(0, 0, 0));
+        frame.canvas.clear();
+
+        let title_style = TextStyle { color: Color::RGB(200, 40, 40), align: Align::Center, ..TextStyle::default() };
+        let title_y = (frame.screen_height / 3) as i32;
+        frame.title_font.draw(frame.canvas, frame.assets, "Game Over", center_x, title_y, &title_style);
+
+        let style = TextStyle { align: Align::Center, ..TextStyle::default() };
+        let prompt_y = (frame.screen_height / 2) as i32;
+        frame.text_font.draw(frame.canvas, frame.assets, "Press interact to return to the title screen", center_x, prompt_y, &style);
+    }
+}
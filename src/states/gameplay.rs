@@ -0,0 +1,98 @@
+extern crate sdl3;
+
+use sdl3::rect::Rect;
+
+use crate::{
+    events::key_handler::{Action, Keys},
+    items::pickup::render_pickups,
+    states::{
+        dialogue::DialogueState,
+        game_over::GameOverState,
+        inventory::InventoryState,
+        pause::PauseState,
+        state::{Frame, Game, State, Transition}
+    },
+    ui::dialogue::Dialogue,
+    world::{StepEvent, GAME_OVER_FLAG}
+};
+
+/// The player walking around the world. Menus and dialogue are pushed on
+/// top, freezing the world until they are closed.
+pub struct GameplayState;
+
+impl State for GameplayState {
+    fn update(&mut self, game: &mut Game, input: &Keys) -> Transition {
+        let world = &mut game.world;
+
+        // ===== MENUS =====
+        if game.presses.was_pressed(Action::Menu) {
+            world.stand_still();
+            return Transition::Push(Box::new(PauseState::new()));
+        }
+        if game.presses.was_pressed(Action::Inventory) && !world.warp_handler.is_transitioning() {
+            world.stand_still();
+            return Transition::Push(Box::new(InventoryState::new()));
+        }
+
+        // ===== WORLD =====
+        match world.step(input, &game.presses) {
+            Some(StepEvent::Warped) => game.teleported = true,
+            Some(StepEvent::Talk(id)) => {
+                if let Some(entry) = world.dialogues.get(&id) {
+                    let dialogue = Dialogue::new(entry);
+                    world.stand_still();
+                    return Transition::Push(Box::new(DialogueState::new(dialogue)));
+                }
+            },
+            None => {}
+        }
+
+        // ===== GAME OVER =====
+        // The flag is lowered again so a save made afterwards can be continued
+        if world.flags.remove(GAME_OVER_FLAG) {
+            return Transition::Reset(Box::new(GameOverState::new()));
+        }
+        Transition::Stay
+    }
+
+    fn render(&self, game: &Game, frame: &mut Frame) {
+        let world = &game.world;
+        let view = frame.camera;
+        let tile_size = world.tile_size;
+        let max_screen_row = frame.screen_height / tile_size;
+        let max_screen_col = frame.screen_width / tile_size;
+
+        view.draw_camera(
+            max_screen_row,
+            max_screen_col,
+            &world.tile_handler,
+            frame.assets,
+            frame.canvas,
+            world.map()
+        );
+        render_pickups(frame.canvas, frame.assets, world, view);                            // Draw items lying on the map
+        frame.entity_sprites.render(frame.canvas, frame.assets, world, frame.alpha, view);  // Draw characters, back to front
+
+        let player_rect = world.player.interpolated_rect(frame.alpha);
+        let hit_box = world.player.hit_box();
+        frame.canvas.draw_rect(view.to_screen(Rect::new(
+            hit_box.x + player_rect.x - world.player.rect.x,
+            hit_box.y + player_rect.y - world.player.rect.y,
+            hit_box.width,
+            hit_box.height
+        ))).ok();
+        view.draw_overlay(                      // Draw roofs and canopies over the player
+            max_screen_row,
+            max_screen_col,
+            &world.tile_handler,
+            frame.assets,
+            frame.canvas,
+            world.map()
+        );
+        world.warp_handler.draw_fade(frame.canvas);   // Fade to black while changing maps
+    }
+
+    fn saves_on_quit(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{
+    events::key_handler::{Action, Keys},
+    states::state::{Frame, Game, State, Transition},
+    ui::inventory_screen::InventoryScreen
+};
+
+/// The inventory screen over the frozen world. The inventory or menu
+/// action closes it.
+#[derive(Default)]
+pub struct InventoryState {
+    screen: InventoryScreen
+}
+
+impl InventoryState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl State for InventoryState {
+    fn update(&mut self, game: &mut Game, _input: &Keys) -> Transition {
+        if game.presses.was_pressed(Action::Inventory) || game.presses.was_pressed(Action::Menu) {
+            return Transition::Pop;
+        }
+        self.screen.update(&game.presses, game.world.player.inventory.slots().len());
+        Transition::Stay
+    }
+
+    fn render(&self, game: &Game, frame: &mut Frame) {
+        self.screen.draw(frame.canvas, frame.assets, frame.text_font, &game.world, frame.screen_width, frame.screen_height);
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,132 @@
+extern crate sdl3;
+
+use sdl3::{keyboard::Keycode, pixels::Color, rect::Rect};
+
+use crate::{
+    events::{
+        key_bindings::{KeyBindings, KEY_BINDINGS_PATH},
+        key_handler::{Action, Keys}
+    },
+    states::state::{Frame, Game, State, Transition},
+    ui::{
+        font::{Align, TextStyle},
+        menu::Menu
+    }
+};
+
+#[derive(Clone, Copy)]
+enum BindingChoice {
+    Rebind(Action),
+    ResetToDefaults,
+    Back
+}
+
+/// Lists the key bound to each action. Choosing an action binds it to the
+/// next key pressed instead, Escape cancels. The menu action is left out
+/// so Escape always reaches the pause menu and this screen. Gamepad
+/// buttons are only changed in the bindings file.
+pub struct KeyBindingsState {
+    menu: Menu<BindingChoice>,
+    waiting: Option<Action>,        // Action to bind to the next key pressed
+    message: Option<String>         // Why the bindings could not be saved
+}
+
+impl KeyBindingsState {
+    pub fn new(game: &Game) -> Self {
+        let mut entries: Vec<_> = Action::ALL.into_iter()
+            .filter(|action| *action != Action::Menu)
+            .map(|action| (String::new(), BindingChoice::Rebind(action)))
+            .collect();
+        entries.push((String::new(), BindingChoice::ResetToDefaults));
+        entries.push((String::new(), BindingChoice::Back));
+
+        let mut menu = Menu::new(entries);
+        menu.relabel(|choice| Self::label(choice, &game.key_bindings));
+        Self { menu, waiting: None, message: None }
+    }
+
+    /// "move_up" as "Move up".
+    fn action_title(action: Action) -> String {
+        let name = action.name().replace('_', " ");
+        let mut chars = name.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => name
+        }
+    }
+
+    fn label(choice: BindingChoice, bindings: &KeyBindings) -> String {
+        match choice {
+            BindingChoice::Rebind(action) => {
+                let keys: Vec<String> = bindings.keys_for(action).iter().map(|key| key.name()).collect();
+                let keys = if keys.is_empty() { "-".to_string() } else { keys.join(", ") };
+                format!("{}: {}", Self::action_title(action), keys)
+            },
+            BindingChoice::ResetToDefaults => "Reset to defaults".to_string(),
+            BindingChoice::Back => "Back".to_string()
+        }
+    }
+
+    /// Relabels the menu and writes the changed bindings to their file.
+    fn bindings_changed(&mut self, game: &Game) {
+        self.menu.relabel(|choice| Self::label(choice, &game.key_bindings));
+        self.message = game.key_bindings.save(KEY_BINDINGS_PATH).err().map(|e| e.to_string());
+    }
+}
+
+impl State for KeyBindingsState {
+    fn update(&mut self, game: &mut Game, _input: &Keys) -> Transition {
+        // ===== REBINDING =====
+        if let Some(action) = self.waiting {
+            if let Some(key) = game.pressed_key {
+                self.waiting = None;
+                if key != Keycode::Escape {
+                    game.key_bindings.set_keys(action, vec![key]);
+                    self.bindings_changed(game);
+                }
+            }
+            return Transition::Stay;
+        }
+
+        if game.presses.was_pressed(Action::Menu) {
+            return Transition::Pop;
+        }
+
+        match self.menu.update(&game.presses) {
+            Some(BindingChoice::Rebind(action)) => self.waiting = Some(action),
+            Some(BindingChoice::ResetToDefaults) => {
+                // Only the keys; gamepad buttons stay as they are
+                let defaults = KeyBindings::default();
+                for action in Action::ALL {
+                    game.key_bindings.set_keys(action, defaults.keys_for(action).to_vec());
+                }
+                self.bindings_changed(game);
+            },
+            Some(BindingChoice::Back) => return Transition::Pop,
+            None => {}
+        }
+        Transition::Stay
+    }
+
+    fn render(&self, _game: &Game, frame: &mut Frame) {
+        let center_x = (frame.screen_width / 2) as i32;
+        frame.canvas.set_draw_color(Color::RGB(20, 20, 40));
+        frame.canvas.clear();
+
+        let title_style = TextStyle { align: Align::Center, ..TextStyle::default() };
+        frame.title_font.draw(frame.canvas, frame.assets, "Key bindings", center_x, 24, &title_style);
+
+        self.menu.draw(frame.canvas, frame.assets, frame.text_font, center_x, 96);
+
+        // ===== PROMPT =====
+        let area = Rect::new(32, frame.screen_height as i32 - 72, frame.screen_width - 64, 64);
+        if let Some(action) = self.waiting {
+            let prompt = format!("Press a key for {}, or Escape to cancel", action.name().replace('_', " "));
+            let style = TextStyle { color: Color::RGB(255, 220, 100), align: Align::Center, ..TextStyle::default() };
+            frame.text_font.draw_in(frame.canvas, frame.assets, &prompt, area, &style);
+        } else if let Some(message) = &self.message {
+            let style = TextStyle { color: Color::RGB(255, 120, 120), align: Align::Center, ..TextStyle::default() };
+            frame.text_font.draw_in(frame.canvas, frame.assets, message, area, &style);
+        }
+    }
+}
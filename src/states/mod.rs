@@ -0,0 +1,9 @@
+pub mod dialogue;
+pub mod game_over;
+pub mod gameplay;
+pub mod inventory;
+pub mod key_bindings;
+pub mod options;
+pub mod pause;
+pub mod state;
+pub mod title;
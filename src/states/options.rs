@@ -0,0 +1,129 @@
+extern crate sdl3;
+
+use sdl3::{pixels::Color, rect::Rect};
+
+use crate::{
+    events::key_handler::{Action, Keys},
+    settings::{Settings, MAX_WINDOW_SCALE, SETTINGS_PATH, VOLUME_STEP},
+    states::{
+        key_bindings::KeyBindingsState,
+        state::{Frame, Game, State, Transition}
+    },
+    ui::{
+        font::{Align, TextStyle},
+        menu::Menu
+    }
+};
+
+#[derive(Clone, Copy)]
+enum OptionsChoice {
+    WindowScale,
+    Fullscreen,
+    Volume,
+    KeyBindings,
+    Back
+}
+
+/// Changes the settings. Left and right adjust the entry under the cursor,
+/// interact steps it forward. Every change is applied and saved at once.
+pub struct OptionsState {
+    menu: Menu<OptionsChoice>,
+    message: Option<String>         // Why the settings could not be saved
+}
+
+impl OptionsState {
+    pub fn new(game: &Game) -> Self {
+        let mut menu = Menu::new(vec![
+            (String::new(), OptionsChoice::WindowScale),
+            (String::new(), OptionsChoice::Fullscreen),
+            (String::new(), OptionsChoice::Volume),
+            (String::new(), OptionsChoice::KeyBindings),
+            (String::new(), OptionsChoice::Back)
+        ]);
+        menu.relabel(|choice| Self::label(choice, &game.settings));
+        Self { menu, message: None }
+    }
+
+    fn label(choice: OptionsChoice, settings: &Settings) -> String {
+        match choice {
+            OptionsChoice::WindowScale => format!("Window scale: {}x", settings.window_scale),
+            OptionsChoice::Fullscreen => format!("Fullscreen: {}", if settings.fullscreen { "On" } else { "Off" }),
+            OptionsChoice::Volume => format!("Volume: {}%", settings.volume),
+            OptionsChoice::KeyBindings => "Key bindings".to_string(),
+            OptionsChoice::Back => "Back".to_string()
+        }
+    }
+
+    /// Steps the setting behind `choice` forward, or back when `forward`
+    /// is false, wrapping around at either end.
+    ///
+    /// # Returns
+    /// Whether `choice` is a setting
+    fn adjust(choice: OptionsChoice, settings: &mut Settings, forward: bool) -> bool {
+        match choice {
+            OptionsChoice::WindowScale => {
+                settings.window_scale = match forward {
+                    true => settings.window_scale % MAX_WINDOW_SCALE + 1,
+                    false => (settings.window_scale + MAX_WINDOW_SCALE - 2) % MAX_WINDOW_SCALE + 1
+                };
+            },
+            OptionsChoice::Fullscreen => settings.fullscreen = !settings.fullscreen,
+            OptionsChoice::Volume => {
+                let steps = 100 / VOLUME_STEP + 1;
+                let step = settings.volume / VOLUME_STEP;
+                let step = if forward { (step + 1) % steps } else { (step + steps - 1) % steps };
+                settings.volume = step * VOLUME_STEP;
+            },
+            OptionsChoice::KeyBindings | OptionsChoice::Back => return false
+        }
+        true
+    }
+}
+
+impl State for OptionsState {
+    fn update(&mut self, game: &mut Game, _input: &Keys) -> Transition {
+        if game.presses.was_pressed(Action::Menu) {
+            return Transition::Pop;
+        }
+
+        // ===== ADJUSTING =====
+        let chosen = self.menu.update(&game.presses);
+        let step = if game.presses.was_pressed(Action::MoveRight) {
+            self.menu.selected().map(|choice| (choice, true))
+        } else if game.presses.was_pressed(Action::MoveLeft) {
+            self.menu.selected().map(|choice| (choice, false))
+        } else {
+            chosen.map(|choice| (choice, true))
+        };
+        if let Some((choice, forward)) = step && Self::adjust(choice, &mut game.settings, forward) {
+            self.menu.relabel(|choice| Self::label(choice, &game.settings));
+            self.message = game.settings.save(SETTINGS_PATH).err().map(|e| e.to_string());
+            return Transition::Stay;
+        }
+
+        match chosen {
+            Some(OptionsChoice::KeyBindings) => Transition::Push(Box::new(KeyBindingsState::new(game))),
+            Some(OptionsChoice::Back) => Transition::Pop,
+            _ => Transition::Stay
+        }
+    }
+
+    fn render(&self, _game: &Game, frame: &mut Frame) {
+        let center_x = (frame.screen_width / 2) as i32;
+        frame.canvas.set_draw_color(Color::RGB(20, 20, 40));
+        frame.canvas.clear();
+
+        let title_style = TextStyle { align: Align::Center, ..TextStyle::default() };
+        let title_y = (frame.screen_height / 6) as i32;
+        frame.title_font.draw(frame.canvas, frame.assets, "Options", center_x, title_y, &title_style);
+
+        self.menu.draw(frame.canvas, frame.assets, frame.text_font, center_x, (frame.screen_height / 3) as i32);
+
+        // ===== ERROR MESSAGE =====
+        if let Some(message) = &self.message {
+            let area = Rect::new(32, frame.screen_height as i32 - 96, frame.screen_width - 64, 64);
+            let style = TextStyle { color: Color::RGB(255, 120, 120), align: Align::Center, ..TextStyle::default() };
+            frame.text_font.draw_in(frame.canvas, frame.assets, message, area, &style);
+        }
+    }
+}
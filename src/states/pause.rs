@@ -0,0 +1,108 @@
+extern crate sdl3;
+
+use sdl3::{
+    pixels::Color,
+    render::BlendMode
+};
+
+use crate::{
+    events::key_handler::{Action, Keys},
+    states::{
+        options::OptionsState,
+        state::{Frame, Game, State, Transition},
+        title::TitleState
+    },
+    ui::{
+        font::{Align, TextStyle},
+        menu::Menu
+    }
+};
+
+#[derive(Clone, Copy)]
+enum PauseChoice {
+    Resume,
+    Save,
+    Options,
+    QuitToTitle,
+    Quit
+}
+
+/// The pause menu over the frozen world. The menu action resumes too.
+/// Quitting the game from here saves it like closing the window does.
+pub struct PauseState {
+    menu: Menu<PauseChoice>,
+    message: Option<String>         // Outcome of the last save
+}
+
+impl Default for PauseState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PauseState {
+    pub fn new() -> Self {
+        let menu = Menu::new(vec![
+            ("Resume".to_string(), PauseChoice::Resume),
+            ("Save game".to_string(), PauseChoice::Save),
+            ("Options".to_string(), PauseChoice::Options),
+            ("Quit to title".to_string(), PauseChoice::QuitToTitle),
+            ("Quit game".to_string(), PauseChoice::Quit)
+        ]);
+        Self { menu, message: None }
+    }
+}
+
+impl State for PauseState {
+    fn update(&mut self, game: &mut Game, _input: &Keys) -> Transition {
+        if game.presses.was_pressed(Action::Menu) {
+            return Transition::Pop;
+        }
+
+        match self.menu.update(&game.presses) {
+            None => Transition::Stay,
+            Some(PauseChoice::Resume) => Transition::Pop,
+            Some(PauseChoice::Save) => {
+                self.message = Some(match game.save() {
+                    Ok(_) => format!("Saved to slot {}", game.slot),
+                    Err(error) => error
+                });
+                Transition::Stay
+            },
+            Some(PauseChoice::Options) => Transition::Push(Box::new(OptionsState::new(game))),
+            Some(PauseChoice::QuitToTitle) => {
+                // Progress is saved like when closing the window
+                if game.saves.is_some() && let Err(error) = game.save() {
+                    self.message = Some(error);
+                    return Transition::Stay;
+                }
+                Transition::Reset(Box::new(TitleState::new(game)))
+            },
+            Some(PauseChoice::Quit) => Transition::Quit
+        }
+    }
+
+    fn render(&self, _game: &Game, frame: &mut Frame) {
+        let center_x = (frame.screen_width / 2) as i32;
+        frame.canvas.set_blend_mode(BlendMode::Blend);
+        frame.canvas.set_draw_color(Color::RGBA(0, 0, 0, 160));
+        frame.canvas.fill_rect(None).ok();
+
+        let title_style = TextStyle { align: Align::Center, ..TextStyle::default() };
+        let title_y = (frame.screen_height / 4) as i32;
+        frame.title_font.draw(frame.canvas, frame.assets, "Paused", center_x, title_y, &title_style);
+
+        let menu_y = (frame.screen_height / 2) as i32;
+        self.menu.draw(frame.canvas, frame.assets, frame.text_font, center_x, menu_y);
+
+        if let Some(message) = &self.message {
+            let message_y = menu_y + self.menu.height(frame.text_font) as i32 + 24;
+            let style = TextStyle { align: Align::Center, ..TextStyle::default() };
+            frame.text_font.draw(frame.canvas, frame.assets, message, center_x, message_y, &style);
+        }
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+}
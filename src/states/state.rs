@@ -0,0 +1,195 @@
+extern crate sdl3;
+
+use std::path::PathBuf;
+
+use sdl3::{keyboard::Keycode, render::Canvas, video::Window};
+
+use crate::{
+    assets::asset_manager::AssetManager,
+    entities::{camera::Camera, entity::EntitySprites, player::Player},
+    events::{
+        key_bindings::KeyBindings,
+        key_handler::{Keys, PressTracker}
+    },
+    save::SaveSlots,
+    settings::Settings,
+    ui::font::Font,
+    world::World
+};
+
+/// Where a new game puts the player.
+pub struct StartPosition {
+    pub map: String,            // Map name
+    pub x: i32,                 // Player's world position
+    pub y: i32,
+    pub player_speed: i32       // Pixels per tick, doubled while running
+}
+
+/// Everything states share that is simulated every tick, with or without
+/// a window, plus the settings the options menu changes.
+pub struct Game {
+    pub world: World,
+    pub presses: PressTracker,      // Updated by `StateStack::update` before any state
+    pub saves: Option<SaveSlots>,   // `None` while saving is disabled
+    pub slot: u32,                  // Save slot continued from and saved to
+    pub teleported: bool,           // Whether the player jumped rather than walked last tick
+    pub settings: Settings,         // Applied to the window whenever they change
+    pub key_bindings: KeyBindings,
+    pub pressed_key: Option<Keycode>,   // Key pressed since the last tick, whatever it is bound to
+    start: StartPosition
+}
+
+impl Game {
+    /// Wraps `world`, which should already be at `start`. Saving stays
+    /// disabled until `saves` is set, and settings and key bindings are the
+    /// defaults until replaced.
+    pub fn new(world: World, start: StartPosition) -> Self {
+        Self {
+            world,
+            presses: PressTracker::new(),
+            saves: None,
+            slot: 1,
+            teleported: false,
+            settings: Settings::default(),
+            key_bindings: KeyBindings::default(),
+            pressed_key: None,
+            start
+        }
+    }
+
+    /// Puts the world back the way it is at the start of the game.
+    pub fn new_game(&mut self) -> Result<(), String> {
+        let player = Player::new(self.start.x, self.start.y, self.start.player_speed, self.world.tile_size);
+        self.world.restart(&self.start.map, player)?;
+        self.teleported = true;
+        Ok(())
+    }
+
+    /// Whether the save slot holds a save to continue from.
+    pub fn has_save(&self) -> bool {
+        self.saves.as_ref().is_some_and(|saves| saves.exists(self.slot))
+    }
+
+    /// Starts a new game and restores the save slot into it.
+    pub fn continue_game(&mut self) -> Result<(), String> {
+        self.new_game()?;
+        let saves = self.saves.as_ref().ok_or("Saving is disabled")?;
+        saves.load(self.slot, &mut self.world).map_err(|e| e.to_string())
+    }
+
+    /// Writes the world to the save slot.
+    ///
+    /// # Returns
+    /// The file written
+    pub fn save(&self) -> Result<PathBuf, String> {
+        let saves = self.saves.as_ref().ok_or("Saving is disabled")?;
+        saves.save(self.slot, &self.world).map_err(|e| e.to_string())?;
+        Ok(saves.path(self.slot))
+    }
+}
+
+/// Everything states draw with, valid for one rendered frame.
+pub struct Frame<'f, 'a> {
+    pub canvas: &'f mut Canvas<Window>,
+    pub assets: &'f mut AssetManager<'a>,
+    pub text_font: &'f mut Font<'a>,        // Dialogue, menus and other small text
+    pub title_font: &'f mut Font<'a>,       // Screen titles
+    pub entity_sprites: &'f EntitySprites,
+    pub camera: &'f Camera,                 // Already interpolated to `alpha`
+    pub alpha: f32,                         // How far into the next tick rendering happens
+    pub screen_width: u32,
+    pub screen_height: u32
+}
+
+/// What the stack should do once a state has been updated.
+pub enum Transition {
+    Stay,
+    Push(Box<dyn State>),       // Put a state on top of this one
+    Pop,                        // Remove this state, uncovering the one below
+    Replace(Box<dyn State>),    // Swap this state for another
+    Reset(Box<dyn State>),      // Remove every state and start over with one
+    Quit                        // Close the game
+}
+
+/// One screen of the game, such as the title screen, gameplay or a menu.
+pub trait State {
+    /// Advances the state by one tick. Only the state on top of the stack
+    /// is updated, so everything below it stays frozen.
+    ///
+    /// # Arguments
+    /// * `game` - Shared game state, with `game.presses` already updated
+    /// * `input` - Actions held during this tick
+    fn update(&mut self, game: &mut Game, input: &Keys) -> Transition;
+
+    /// Draws the state.
+    fn render(&self, game: &Game, frame: &mut Frame);
+
+    /// Whether the states below stay visible beneath this one. They are
+    /// drawn first, down to the first state that is not an overlay.
+    fn is_overlay(&self) -> bool {
+        false
+    }
+
+    /// Whether quitting the game while this state is on the stack should
+    /// save it first.
+    fn saves_on_quit(&self) -> bool {
+        false
+    }
+}
+
+/// The states of the game, the active one on top.
+pub struct StateStack {
+    states: Vec<Box<dyn State>>,
+    quit: bool
+}
+
+impl StateStack {
+    /// Starts with `initial` as the only state.
+    pub fn new(initial: Box<dyn State>) -> Self {
+        Self { states: vec![initial], quit: false }
+    }
+
+    /// Whether the game is still running, i.e. no state has quit.
+    pub fn is_running(&self) -> bool {
+        !self.quit && !self.states.is_empty()
+    }
+
+    /// Whether any state on the stack wants the game saved on quit.
+    pub fn saves_on_quit(&self) -> bool {
+        self.states.iter().any(|state| state.saves_on_quit())
+    }
+
+    /// Advances the top state by one tick and applies its transition.
+    pub fn update(&mut self, game: &mut Game, input: &Keys) {
+        game.presses.update(input);
+        game.teleported = false;
+        let Some(state) = self.states.last_mut() else {
+            return;
+        };
+
+        let transition = state.update(game, input);
+        game.pressed_key = None;
+        match transition {
+            Transition::Stay => {},
+            Transition::Push(state) => self.states.push(state),
+            Transition::Pop => {
+                self.states.pop();
+            },
+            Transition::Replace(state) => {
+                self.states.pop();
+                self.states.push(state);
+            },
+            Transition::Reset(state) => self.states = vec![state],
+            Transition::Quit => self.quit = true
+        }
+    }
+
+    /// Draws the top state and every overlay-covered state beneath it,
+    /// bottom first.
+    pub fn render(&self, game: &Game, frame: &mut Frame) {
+        let first = self.states.iter().rposition(|state| !state.is_overlay()).unwrap_or(0);
+        for state in &self.states[first..] {
+            state.render(game, frame);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+extern crate sdl3;
+
+use sdl3::{pixels::Color, rect::Rect};
+
+use crate::{
+    events::key_handler::Keys,
+    states::{
+        gameplay::GameplayState,
+        options::OptionsState,
+        state::{Frame, Game, State, Transition}
+    },
+    ui::{
+        font::{Align, TextStyle},
+        menu::Menu
+    }
+};
+
+/// Name shown on the title screen.
+const GAME_TITLE: &str = "2D Adventure";
+
+#[derive(Clone, Copy)]
+enum TitleChoice {
+    Continue,
+    NewGame,
+    Options,
+    Quit
+}
+
+/// The first screen: continue from the save slot, start a new game, change
+/// the options or quit.
+pub struct TitleState {
+    menu: Menu<TitleChoice>,
+    message: Option<String>         // Why the last choice failed
+}
+
+impl TitleState {
+    /// Offers to continue only if the save slot holds a save.
+    pub fn new(game: &Game) -> Self {
+        Self { menu: Self::menu(game.has_save()), message: None }
+    }
+
+    fn menu(can_continue: bool) -> Menu<TitleChoice> {
+        let mut entries = Vec::new();
+        if can_continue {
+            entries.push(("Continue".to_string(), TitleChoice::Continue));
+        }
+        entries.push(("New game".to_string(), TitleChoice::NewGame));
+        entries.push(("Options".to_string(), TitleChoice::Options));
+        entries.push(("Quit".to_string(), TitleChoice::Quit));
+        Menu::new(entries)
+    }
+}
+
+impl State for TitleState {
+    fn update(&mut self, game: &mut Game, _input: &Keys) -> Transition {
+        let choice = match self.menu.update(&game.presses) {
+            None => return Transition::Stay,
+            Some(TitleChoice::Options) => return Transition::Push(Box::new(OptionsState::new(game))),
+            Some(TitleChoice::Quit) => return Transition::Quit,
+            Some(choice) => choice
+        };
+        let result = match choice {
+            TitleChoice::Continue => game.continue_game(),
+            _ => game.new_game()
+        };
+
+        match result {
+            Ok(()) => Transition::Replace(Box::new(GameplayState)),
+            Err(error) if matches!(choice, TitleChoice::Continue) => {
+                // A save that cannot be loaded is kept as it is rather than
+                // saved over, so saving stays off for the rest of the run
+                game.saves = None;
+                self.message = Some(format!("{} The save will not be overwritten.", error));
+                self.menu = Self::menu(false);
+                Transition::Stay
+            },
+            Err(error) => {
+                self.message = Some(error);
+                Transition::Stay
+            }
+        }
+    }
+
+    fn render(&self, _game: &Game, frame: &mut Frame) {
+        let center_x = (frame.screen_width / 2) as i32;
+        frame.canvas.set_draw_color(Color::RGB(20, 20, 40));
+        frame.canvas.clear();
+
+        let title_style = TextStyle { align: Align::Center, ..TextStyle::default() };
+        let title_y = (frame.screen_height / 4) as i32;
+        frame.title_font.draw(frame.canvas, frame.assets, GAME_TITLE, center_x, title_y, &title_style);
+
+        self.menu.draw(frame.canvas, frame.assets, frame.text_font, center_x, (frame.screen_height / 2) as i32);
+
+        // ===== ERROR MESSAGE =====
+        if let Some(message) = &self.message {
+            let area = Rect::new(32, frame.screen_height as i32 - 96, frame.screen_width - 64, 64);
+            let style = TextStyle { color: Color::RGB(255, 120, 120), align: Align::Center, ..TextStyle::default() };
+            frame.text_font.draw_in(frame.canvas, frame.assets, message, area, &style);
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use std::{
+    fmt, fs,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf}
+};
+
+use crate::tiles::tile_handler::{LayerKind, Map, MapLayer, Pixel};
+
+/// Everything that can go wrong while reading a map grid.
+///
+/// Line and column numbers are 1-based and refer to the source text, so they
+/// can be matched directly against what an editor shows.
+#[derive(Debug)]
+pub enum MapLoadError {
+    Io { path: PathBuf, message: String },
+    InvalidToken { path: PathBuf, line: usize, column: usize, token: String },
+    UnknownTile { path: PathBuf, line: usize, column: usize, index: u32, tile_count: u32 },
+    RaggedRow { path: PathBuf, line: usize, expected: usize, found: usize },
+    Empty { path: PathBuf },
+    Format { path: PathBuf, message: String },
+    UnknownGid { path: PathBuf, layer: String, x: usize, y: usize, gid: u32 }
+}
+
+impl fmt::Display for MapLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapLoadError::Io { path, message } => {
+                write!(f, "{}: failed to read map: {}", path.display(), message)
+            },
+            MapLoadError::InvalidToken { path, line, column, token } => {
+                write!(f, "{}:{}:{}: '{}' is not a tile index", path.display(), line, column, token)
+            },
+            MapLoadError::UnknownTile { path, line, column, index, tile_count } => {
+                write!(
+                    f,
+                    "{}:{}:{}: tile index {} does not exist (only {} tiles are defined)",
+                    path.display(), line, column, index, tile_count
+                )
+            },
+            MapLoadError::RaggedRow { path, line, expected, found } => {
+                write!(
+                    f,
+                    "{}:{}: row has {} tiles, but the first row has {}",
+                    path.display(), line, found, expected
+                )
+            },
+            MapLoadError::Empty { path } => {
+                write!(f, "{}: map contains no tiles", path.display())
+            },
+            MapLoadError::Format { path, message } => {
+                write!(f, "{}: invalid map: {}", path.display(), message)
+            },
+            MapLoadError::UnknownGid { path, layer, x, y, gid } => {
+                write!(
+                    f,
+                    "{}: layer '{}' cell ({}, {}): gid {} does not match any tile in the manifest",
+                    path.display(), layer, x, y, gid
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for MapLoadError {}
+
+impl Map {
+    /// Reads a map grid from a file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to a whitespace-separated grid of tile indices
+    /// * `tile_size` - Size of one tile in screen pixels
+    /// * `tile_count` - Number of tiles in `TileHandler::tiles`; larger indices are rejected
+    pub fn load(path: impl AsRef<Path>, tile_size: u32, tile_count: u32) -> Result<Self, MapLoadError> {
+        let path = path.as_ref();
+        let file = fs::File::open(path).map_err(|e| MapLoadError::Io {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+
+        Self::from_reader(BufReader::new(file), path, tile_size, tile_count)
+    }
+
+    /// Reads a map grid from any buffered reader.
+    ///
+    /// Each non-blank line is one row of the map. Every row must have the same
+    /// number of tiles and every tile must be an index below `tile_count`,
+    /// or `.` for an empty cell. `path` is only used to label errors.
+    ///
+    /// A file holding a single grid becomes one ground layer. Further layers
+    /// are introduced by a header line naming the layer kind and optionally
+    /// overriding its collision, e.g. `[decoration]` or `[overlay collision]`.
+    /// All layers must be the same size.
+    pub fn from_reader(
+        reader: impl BufRead,
+        path: &Path,
+        tile_size: u32,
+        tile_count: u32
+    ) -> Result<Self, MapLoadError> {
+        let mut map = Map::named_after(path);
+        let mut first_row_len = None;
+        let mut last_line = 0;
+
+        for (line_index, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| MapLoadError::Io {
+                path: path.to_path_buf(),
+                message: e.to_string()
+            })?;
+            let line_number = line_index + 1;
+            last_line = line_number;
+
+            // Blank lines (usually a trailing newline) do not start a row
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            // ===== LAYER HEADERS =====
+            if let Some(header) = line.trim().strip_prefix('[') {
+                let layer = parse_layer_header(header, path, line_number)?;
+                if let Some(previous) = map.layers.last() {
+                    check_layer_height(previous, &map, path, line_number)?;
+                }
+                map.layers.push(layer);
+                continue;
+            }
+            if map.layers.is_empty() {
+                map.layers.push(MapLayer::new(String::from("ground"), LayerKind::Ground));
+            }
+
+            let layer = map.layers.last_mut().unwrap();
+            let row = layer.map.len();
+            let mut col_vec = Vec::new();
+
+            for (column, token) in tokens_with_columns(&line) {
+                if token == "." {
+                    col_vec.push(None);
+                    continue;
+                }
+
+                let index: u32 = token.parse().map_err(|_| MapLoadError::InvalidToken {
+                    path: path.to_path_buf(),
+                    line: line_number,
+                    column,
+                    token: token.to_string()
+                })?;
+
+                if index >= tile_count {
+                    return Err(MapLoadError::UnknownTile {
+                        path: path.to_path_buf(),
+                        line: line_number,
+                        column,
+                        index,
+                        tile_count
+                    });
+                }
+
+                col_vec.push(Some(
+                    Pixel::new(
+                        col_vec.len() as i32 * tile_size as i32,
+                        row as i32 * tile_size as i32,
+                        index
+                    )
+                ));
+            }
+
+            let expected = *first_row_len.get_or_insert(col_vec.len());
+            if expected != col_vec.len() {
+                return Err(MapLoadError::RaggedRow {
+                    path: path.to_path_buf(),
+                    line: line_number,
+                    expected,
+                    found: col_vec.len()
+                });
+            }
+
+            layer.map.push(col_vec);
+        }
+
+        if first_row_len.is_none() {
+            return Err(MapLoadError::Empty { path: path.to_path_buf() });
+        }
+        if let Some(last) = map.layers.last() {
+            check_layer_height(last, &map, path, last_line)?;
+        }
+
+        Ok(map)
+    }
+}
+
+/// Parses the inside of a `[kind flags...]` layer header.
+fn parse_layer_header(header: &str, path: &Path, line: usize) -> Result<MapLayer, MapLoadError> {
+    let header_error = |message: String| MapLoadError::Format {
+        path: path.to_path_buf(),
+        message: format!("line {}: {}", line, message)
+    };
+
+    let header = header.strip_suffix(']')
+        .ok_or_else(|| header_error(String::from("layer header is missing ']'")))?;
+    let mut words = header.split_whitespace();
+    let kind_name = words.next()
+        .ok_or_else(|| header_error(String::from("layer header has no kind")))?;
+    let kind = LayerKind::from_name(kind_name)
+        .ok_or_else(|| header_error(format!("'{}' is not a layer kind", kind_name)))?;
+
+    let mut layer = MapLayer::new(kind_name.to_string(), kind);
+    for flag in words {
+        match flag {
+            "collision" => layer.collision = true,
+            "nocollision" => layer.collision = false,
+            other => return Err(header_error(format!("unknown layer flag '{}'", other)))
+        }
+    }
+    Ok(layer)
+}
+
+/// Checks that a finished layer has as many rows as the first layer.
+fn check_layer_height(layer: &MapLayer, map: &Map, path: &Path, line: usize) -> Result<(), MapLoadError> {
+    let expected = map.layers[0].map.len();
+    if layer.map.len() != expected {
+        return Err(MapLoadError::Format {
+            path: path.to_path_buf(),
+            message: format!(
+                "line {}: layer '{}' has {} rows, but the first layer has {}",
+                line, layer.name, layer.map.len(), expected
+            )
+        });
+    }
+    Ok(())
+}
+
+/// Splits a line on whitespace, pairing each token with its 1-based
+/// character column.
+fn tokens_with_columns(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (column, (byte, c)) in line.char_indices().enumerate() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some((column, byte)),
+            (true, Some((start_column, start_byte))) => {
+                tokens.push((start_column + 1, &line[start_byte..byte]));
+                start = None;
+            },
+            _ => ()
+        }
+    }
+    if let Some((start_column, start_byte)) = start {
+        tokens.push((start_column + 1, &line[start_byte..]));
+    }
+
+    tokens.into_iter()
+}
@@ -1 +1,4 @@
-pub mod tile_handler;
\ No newline at end of file
+pub mod tile_handler;
+pub mod tile_manifest;
+pub mod map_loader;
+pub mod tiled_loader;
\ No newline at end of file
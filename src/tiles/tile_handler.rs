@@ -1,153 +1,240 @@
 extern crate sdl3;
 
-use std::{fs, io::BufReader};
-use std::io::BufRead;
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use sdl3::{ 
-    render::{Texture, TextureCreator}, 
-    surface::Surface, 
-    video::{WindowContext}
-};
+use crate::assets::asset_manager::{AssetManager, TextureHandle};
+use crate::tiles::map_loader::MapLoadError;
+use crate::tiles::tiled_loader;
+use crate::tiles::tile_manifest::{TileDefinition, TileManifest, TILE_MANIFEST_PATH};
 
 pub struct Pixel{
     pub x: i32,
     pub y: i32,
-    pub index: u32
+    pub index: u32,
+    pub solid: Option<bool>     // Per-cell override of `Tile::immovable`
 }
 
-pub struct Tile<'a> {
-    pub image: Texture<'a>,
+/// A tile's properties, plus a handle to its texture once loaded. Maps and
+/// collision only need the properties, so they work without a window.
+pub struct Tile {
+    pub id: u32,
+    pub name: String,
+    pub image: PathBuf,
+    pub texture: Option<TextureHandle>,     // Set by `TileHandler::load_textures`
     pub immovable: bool
 }
-// [[i32; 16]; 12]
+/// Where a layer is drawn relative to the entities on the map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayerKind {
+    Ground,         // Drawn first, beneath the player
+    Decoration,     // Drawn over the ground, still beneath the player
+    Overlay         // Drawn above the player (roofs, tree canopies)
+}
+
+/// One grid of tiles. Empty cells are `None` and let lower layers show through.
+pub struct MapLayer {
+    pub name: String,
+    pub kind: LayerKind,
+    pub collision: bool,                // Whether solid tiles in this layer block movement
+    pub map: Vec<Vec<Option<Pixel>>>
+}
+
+/// A map made of equally sized layers, ordered bottom to top.
 pub struct Map{
-    pub map: Vec<Vec<Pixel>>
+    pub name: String,           // File name without extension, used by warps
+    pub layers: Vec<MapLayer>
 }
 
-pub struct TileHandler<'a>{
-    pub tiles: Vec<Tile<'a>>,
+pub struct TileHandler{
+    pub tiles: Vec<Tile>,
     pub tile_size: u32,
     pub maps: Vec<Map>
 
 }
 
 impl Pixel{
-    fn new(x: i32, y: i32, index: u32) -> Self {
-        Self { x, y, index}
+    pub(crate) fn new(x: i32, y: i32, index: u32) -> Self {
+        Self { x, y, index, solid: None }
+    }
+
+    /// Whether this cell blocks movement, honouring any per-cell override.
+    pub fn is_solid(&self, tiles: &[Tile]) -> bool {
+        self.solid.unwrap_or(tiles[self.index as usize].immovable)
+    }
+}
+
+impl LayerKind {
+    /// Parses a layer kind as written in map files (`ground`, `decoration`, `overlay`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "ground" => Some(LayerKind::Ground),
+            "decoration" => Some(LayerKind::Decoration),
+            "overlay" => Some(LayerKind::Overlay),
+            _ => None
+        }
+    }
+
+    /// Ground and decoration block movement unless told otherwise; overlays
+    /// sit above the player and never do.
+    pub fn default_collision(&self) -> bool {
+        *self != LayerKind::Overlay
+    }
+}
+
+impl MapLayer {
+    pub fn new(name: String, kind: LayerKind) -> Self {
+        Self { name, kind, collision: kind.default_collision(), map: Vec::new() }
     }
 }
 
 impl Map {
+    /// Creates an empty map named after the file at `path`.
+    pub(crate) fn named_after(path: &Path) -> Self {
+        let name = path.file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self { name, layers: Vec::new() }
+    }
+
     pub fn row_len(&self) -> u32{
-        self.map.len() as u32
+        self.layers[0].map.len() as u32
     }
 
     pub fn col_len(&self) -> u32{
-        self.map[0].len() as u32
+        self.layers[0].map[0].len() as u32
+    }
+
+    /// Whether any collision-bearing layer has a solid tile at `row`, `col`.
+    ///
+    /// # Returns
+    /// `None` if the cell lies outside the map
+    pub fn is_solid(&self, row: usize, col: usize, tiles: &[Tile]) -> Option<bool> {
+        if row >= self.row_len() as usize || col >= self.col_len() as usize {
+            return None;
+        }
+        Some(self.layers.iter()
+            .filter(|layer| layer.collision)
+            .filter_map(|layer| layer.map[row][col].as_ref())
+            .any(|pixel| pixel.is_solid(tiles)))
     }
 }
 
-impl<'a> Tile<'a> {
-    fn new(image: Texture<'a>) -> Self {
+impl Tile {
+    fn new(definition: &TileDefinition) -> Self {
         Self { 
-            image: image,
-            immovable: false
+            id: definition.id,
+            name: definition.name.clone(),
+            image: definition.image.clone(),
+            texture: None,
+            immovable: definition.solid
         }
     }
 }
 
-impl<'a> TileHandler<'a> {
+impl TileHandler {
 
-    pub fn new(tile_size: u32, texture_creator: &'a TextureCreator<WindowContext>) -> Self {
+    /// Loads the tile manifest and every map. No textures are created, so
+    /// this also works in headless simulations.
+    pub fn new(tile_size: u32) -> Result<Self, String> {
         let mut tile_handler = Self { tiles: Vec::new() , tile_size: tile_size, maps: Vec::new()};
-        tile_handler.load_tiles(texture_creator);
-        tile_handler.load_maps();
-        tile_handler.tiles[1].immovable = true;
-        tile_handler.tiles[2].immovable = true;
-        tile_handler.tiles[4].immovable = true;
-        tile_handler
+        let manifest = TileManifest::load(TILE_MANIFEST_PATH)
+            .map_err(|e| e.to_string())?;
+        tile_handler.load_tiles(&manifest);
+        tile_handler.load_maps(&manifest)
+            .map_err(|e| e.to_string())?;
+        Ok(tile_handler)
     }
     
-    fn load_tiles(&mut self, texture_creator: &'a TextureCreator<WindowContext>){
-        let mut tile_paths = Vec::new();
-
-        for entry in fs::read_dir("res/tiles").unwrap(){
-            let path = entry
-                .ok()
-                .unwrap()
-                .path();
-            
-            if path.is_file(){
-                let mut _path = String::new();
-                _path.insert_str(0, path.to_str().unwrap());
-                tile_paths.push(_path);
-            }
-
-        }
-        tile_paths.sort();
-        println!("{tile_paths:?}");
-
-        // Load each BMP file and convert it to a GPU texture
-        for path in tile_paths {
-            let texture = Surface::load_bmp(path)
-                .unwrap()
-                .as_texture(texture_creator)
-                .ok()
-                .unwrap();
-            self.tiles.push(Tile::new(texture));
+    /// Registers every tile declared in the manifest.
+    ///
+    /// Tiles are stored by id, so `tiles[n]` is always the tile that map
+    /// files refer to as `n`, regardless of file names or manifest order.
+    fn load_tiles(&mut self, manifest: &TileManifest) {
+        for definition in &manifest.tiles {
+            self.tiles.push(Tile::new(definition));
         }
+    }
 
+    /// Loads every tile's image through `assets`. Not needed (nor possible)
+    /// in headless simulations.
+    pub fn load_textures(&mut self, assets: &mut AssetManager) {
+        for tile in &mut self.tiles {
+            tile.texture = Some(assets.load_texture(&tile.image));
+        }
     }
 
-    fn load_maps(&mut self){
+    /// Loads every map in `res/maps`, in file name order.
+    ///
+    /// `.txt` files are plain index grids; `.tmj` and `.tmx` files are
+    /// imported from the Tiled editor, as are `.json` files Tiled saved as a
+    /// map. Other files (such as external Tiled tilesets, whether `.tsx`,
+    /// `.tsj` or `.json`) are skipped.
+    fn load_maps(&mut self, manifest: &TileManifest) -> Result<(), MapLoadError> {
         let mut map_paths = Vec::new();
 
-        for entry in fs::read_dir("res/maps").unwrap(){
+        let entries = fs::read_dir("res/maps").map_err(|e| MapLoadError::Io {
+            path: PathBuf::from("res/maps"),
+            message: e.to_string()
+        })?;
+        for entry in entries {
             let path = entry
-                .ok()
-                .unwrap()
+                .map_err(|e| MapLoadError::Io {
+                    path: PathBuf::from("res/maps"),
+                    message: e.to_string()
+                })?
                 .path();
             
-            if path.is_file(){
-                let mut _path = String::new();
-                _path.insert_str(0, path.to_str().unwrap());
-                map_paths.push(_path);
+            if !path.is_file() {
+                continue;
+            }
+            let is_map = match path.extension().and_then(|e| e.to_str()) {
+                Some("txt" | "tmj" | "tmx") => true,
+                Some("json") => tiled_loader::is_tiled_json_map(&path)?,
+                _ => false
+            };
+            if is_map {
+                map_paths.push(path);
             }
         }
         map_paths.sort();
-        println!("{map_paths:?}");
 
         for path in map_paths{
-            let f = fs::File::open(path).ok().unwrap();
-            let mut reader = BufReader::new(f);
-            let mut map = Map{map: Vec::new()};
-            let mut row = 0;
-            
-            'loop1: loop{
-                let mut line = String::new();
-                let len = reader.read_line(&mut line)
-                    .expect("failed to read");
-                if len == 0{break 'loop1;}
-                let mut col_vec = Vec::new();
-                
-                'loop2: for tile in line.split_whitespace(){
-                    if tile == "\n" {continue 'loop2}
-
-                    col_vec.push(
-                        Pixel::new(
-                            col_vec.len() as i32 * self.tile_size as i32, 
-                            row as i32 * self.tile_size as i32,
-                            tile.parse().unwrap()
-                        )
-                    );
-                }
-
-                map.map.insert(row, col_vec);
-                row += 1;
-
-            }
+            let map = self.load_map(&path, manifest)?;
             self.maps.push(map);
         }
+
+        Ok(())
+    }
+
+    /// Parses one map file, picking the loader by its extension.
+    fn load_map(&self, path: &Path, manifest: &TileManifest) -> Result<Map, MapLoadError> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("txt") => Map::load(path, self.tile_size, manifest.tiles.len() as u32),
+            _ => Map::load_tiled(path, self.tile_size, &manifest.tiles)
+        }
+    }
+
+    /// Loads the map file at `path` again and swaps it in for the loaded map
+    /// of the same name, so its index (and the player standing on it) stay put.
+    ///
+    /// # Returns
+    /// `Ok(false)` if no loaded map came from `path`. On error the old map
+    /// is kept.
+    pub fn reload_map(&mut self, path: &Path) -> Result<bool, String> {
+        let Some(index) = self.map_index(&Map::named_after(path).name) else {
+            return Ok(false);
+        };
+        let manifest = TileManifest::load(TILE_MANIFEST_PATH)
+            .map_err(|e| e.to_string())?;
+        self.maps[index] = self.load_map(path, &manifest)
+            .map_err(|e| e.to_string())?;
+        Ok(true)
+    }
+
+    /// Returns the position in `maps` of the map called `name`.
+    pub fn map_index(&self, name: &str) -> Option<usize> {
+        self.maps.iter().position(|map| map.name == name)
     }
 
     // pub fn draw_map(&self, max_screen_col: u32 ,max_screen_row: u32, canvas: &mut Canvas<Window>, map: &Map){
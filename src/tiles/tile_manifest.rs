@@ -0,0 +1,158 @@
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf}
+};
+
+use serde::Deserialize;
+
+use crate::assets::image_loader;
+
+/// Default location of the tile manifest, relative to the working directory.
+pub const TILE_MANIFEST_PATH: &str = "res/tiles/tiles.toml";
+
+/// A single tile declaration as written in the manifest file.
+///
+/// Unknown keys are rejected so a typo such as `sold = true` is reported
+/// instead of silently producing a walkable tile.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TileDefinition {
+    pub id: u32,                // Index used by the map files
+    pub name: String,           // Human readable name, used in error messages
+    pub image: PathBuf,         // Image path, relative to the manifest directory
+    #[serde(default)]
+    pub solid: bool             // Whether the tile blocks movement
+}
+
+/// Top level layout of the manifest: a list of `[[tile]]` tables.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TileManifestFile {
+    #[serde(default)]
+    tile: Vec<TileDefinition>
+}
+
+/// Validated tile manifest.
+///
+/// `tiles` is sorted by id and every id from 0 to `tiles.len() - 1` is
+/// present exactly once, so a tile's id is also its position in the list.
+/// Image paths are resolved against the manifest directory and known to exist.
+#[derive(Debug)]
+pub struct TileManifest {
+    pub tiles: Vec<TileDefinition>
+}
+
+/// Everything that can go wrong while reading the tile manifest.
+#[derive(Debug)]
+pub enum TileLoadError {
+    Io { path: PathBuf, message: String },
+    Parse { path: PathBuf, message: String },
+    DuplicateId { id: u32, first: String, second: String },
+    MissingId { id: u32 },
+    MissingImage { id: u32, name: String, path: PathBuf },
+    UnsupportedImage { id: u32, name: String, path: PathBuf }
+}
+
+impl fmt::Display for TileLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TileLoadError::Io { path, message } => {
+                write!(f, "{}: failed to read tile manifest: {}", path.display(), message)
+            },
+            TileLoadError::Parse { path, message } => {
+                write!(f, "{}: invalid tile manifest: {}", path.display(), message)
+            },
+            TileLoadError::DuplicateId { id, first, second } => {
+                write!(f, "tile id {} is used by both '{}' and '{}'", id, first, second)
+            },
+            TileLoadError::MissingId { id } => {
+                write!(f, "tile ids must be contiguous from 0, but id {} is not defined", id)
+            },
+            TileLoadError::MissingImage { id, name, path } => {
+                write!(f, "tile {} ('{}'): image {} does not exist", id, name, path.display())
+            },
+            TileLoadError::UnsupportedImage { id, name, path } => {
+                write!(f, "tile {} ('{}'): {} is not a supported image format", id, name, path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for TileLoadError {}
+
+impl TileManifest {
+    /// Reads and validates a tile manifest from disk.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the TOML manifest file
+    ///
+    /// # Returns
+    /// The validated manifest, or the first problem found in it
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TileLoadError> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path).map_err(|e| TileLoadError::Io {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+        let base_dir = path.parent().unwrap_or(Path::new("."));
+
+        Self::parse(&source, path, base_dir)
+    }
+
+    /// Parses manifest text, resolving image paths against `base_dir`.
+    ///
+    /// `path` is only used to label errors.
+    pub fn parse(source: &str, path: &Path, base_dir: &Path) -> Result<Self, TileLoadError> {
+        let file: TileManifestFile = toml::from_str(source).map_err(|e| TileLoadError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+
+        // ===== DUPLICATE IDS =====
+        let mut names_by_id: HashMap<u32, &str> = HashMap::new();
+        for definition in &file.tile {
+            if let Some(first) = names_by_id.insert(definition.id, &definition.name) {
+                return Err(TileLoadError::DuplicateId {
+                    id: definition.id,
+                    first: first.to_string(),
+                    second: definition.name.clone()
+                });
+            }
+        }
+
+        // ===== CONTIGUOUS IDS =====
+        // Maps store tile ids directly, so the ids double as indices into
+        // `TileHandler::tiles` and may not leave holes.
+        for id in 0..file.tile.len() as u32 {
+            if !names_by_id.contains_key(&id) {
+                return Err(TileLoadError::MissingId { id });
+            }
+        }
+
+        let mut tiles = file.tile;
+        tiles.sort_by_key(|definition| definition.id);
+
+        // ===== IMAGE PATHS =====
+        for definition in &mut tiles {
+            let image = base_dir.join(&definition.image);
+            if !image.is_file() {
+                return Err(TileLoadError::MissingImage {
+                    id: definition.id,
+                    name: definition.name.clone(),
+                    path: image
+                });
+            }
+            if !image_loader::is_image(&image) {
+                return Err(TileLoadError::UnsupportedImage {
+                    id: definition.id,
+                    name: definition.name.clone(),
+                    path: image
+                });
+            }
+            definition.image = image;
+        }
+
+        Ok(Self { tiles })
+    }
+}
@@ -0,0 +1,726 @@
+use std::{
+    collections::HashMap,
+    fs,
+    io::Read,
+    path::{Path, PathBuf}
+};
+
+use base64::Engine;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use quick_xml::events::{BytesStart, Event};
+use serde::Deserialize;
+
+use crate::tiles::{
+    map_loader::MapLoadError,
+    tile_handler::{LayerKind, Map, MapLayer, Pixel},
+    tile_manifest::TileDefinition
+};
+
+// Tiled stores flip/rotation flags in the top bits of every gid
+const GID_FLAG_MASK: u32 = 0xF000_0000;
+
+/// A custom property value attached to a Tiled tile or layer.
+#[derive(Debug, Clone)]
+enum TiledProperty {
+    Bool(bool),
+    Int(i64),
+    Text(String)
+}
+
+/// One tile inside a Tiled tileset.
+#[derive(Debug)]
+struct TiledTile {
+    id: u32,
+    image: Option<String>,
+    properties: HashMap<String, TiledProperty>
+}
+
+/// A tileset with its tiles, after resolving external `.tsx`/`.tsj` files.
+///
+/// Image tilesets cut one image into `tilecount` tiles, and only list the
+/// tiles that have properties. Collections of images list every tile.
+#[derive(Debug)]
+struct TiledTileset {
+    firstgid: u32,
+    image: Option<String>,      // The whole sheet, for image tilesets
+    tilecount: u32,             // Tiles cut from `image`
+    tiles: Vec<TiledTile>
+}
+
+/// A decoded tile layer: `width * height` gids in row-major order.
+#[derive(Debug)]
+struct TiledLayer {
+    name: String,
+    properties: HashMap<String, TiledProperty>,
+    data: Vec<u32>
+}
+
+/// Format-independent view of a Tiled map.
+#[derive(Debug)]
+struct TiledMap {
+    width: usize,
+    height: usize,
+    tilesets: Vec<TiledTileset>,
+    layers: Vec<TiledLayer>
+}
+
+/// What a Tiled gid turns into on our side.
+#[derive(Clone, Copy)]
+struct ResolvedTile {
+    index: u32,
+    solid: Option<bool>
+}
+
+impl Map {
+    /// Imports a map saved by the Tiled editor.
+    ///
+    /// Both JSON (`.tmj`, `.json`) and XML (`.tmx`) maps are accepted, with
+    /// layers stored as CSV or base64 (optionally zlib/gzip compressed).
+    /// Tiled tiles are matched to entries of the tile manifest either by a
+    /// `tile` custom property (manifest name or id) or by image file name.
+    /// In a tileset cut from one image, tile `n` of the image (counted
+    /// left to right, top to bottom) is manifest tile `n` unless it has a
+    /// `tile` property, so such a sheet should be laid out in manifest
+    /// order. A boolean `solid` custom property on a Tiled tile overrides
+    /// the manifest's solidity for every cell using it.
+    ///
+    /// Every tile layer becomes a `MapLayer`, in Tiled's draw order. Layers
+    /// may set a `kind` string property (`ground`, `decoration`, `overlay`)
+    /// and a `collision` bool property.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the Tiled map file
+    /// * `tile_size` - Size of one tile in screen pixels
+    /// * `definitions` - Tile manifest entries, indexed by id
+    pub fn load_tiled(
+        path: impl AsRef<Path>,
+        tile_size: u32,
+        definitions: &[TileDefinition]
+    ) -> Result<Self, MapLoadError> {
+        let path = path.as_ref();
+        let source = read_source(path)?;
+
+        let tiled = match extension(path).as_str() {
+            "tmx" => parse_tmx(&source, path)?,
+            _ => parse_tmj(&source, path)?
+        };
+
+        build_map(&tiled, path, tile_size, definitions)
+    }
+}
+
+/// Whether the JSON file at `path` is a Tiled map rather than a tileset
+/// (or anything else) saved next to the maps. Tiled marks maps with
+/// `"type": "map"`.
+pub(crate) fn is_tiled_json_map(path: &Path) -> Result<bool, MapLoadError> {
+    #[derive(Deserialize)]
+    struct JsonHeader {
+        #[serde(default, rename = "type")]
+        kind: String
+    }
+
+    let source = read_source(path)?;
+    let header: JsonHeader = serde_json::from_str(&source)
+        .map_err(|e| format_error(path, e.to_string()))?;
+    Ok(header.kind == "map")
+}
+
+/// Returns the lowercase extension of `path`, or an empty string.
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase()
+}
+
+fn read_source(path: &Path) -> Result<String, MapLoadError> {
+    fs::read_to_string(path).map_err(|e| MapLoadError::Io {
+        path: path.to_path_buf(),
+        message: e.to_string()
+    })
+}
+
+fn format_error(path: &Path, message: impl Into<String>) -> MapLoadError {
+    MapLoadError::Format { path: path.to_path_buf(), message: message.into() }
+}
+
+/// Turns a decoded Tiled map into our `Map`.
+fn build_map(
+    tiled: &TiledMap,
+    path: &Path,
+    tile_size: u32,
+    definitions: &[TileDefinition]
+) -> Result<Map, MapLoadError> {
+    if tiled.layers.is_empty() || tiled.width == 0 || tiled.height == 0 {
+        return Err(MapLoadError::Empty { path: path.to_path_buf() });
+    }
+
+    // ===== GID LOOKUP =====
+    let mut resolved: HashMap<u32, ResolvedTile> = HashMap::new();
+    for tileset in &tiled.tilesets {
+        // Every tile cut from a sheet stands for the manifest tile with its index
+        let sheet_index = |id: u32| {
+            (tileset.image.is_some() && id < tileset.tilecount && (id as usize) < definitions.len()).then_some(id)
+        };
+        if tileset.image.is_some() {
+            for id in (0..tileset.tilecount).filter_map(sheet_index) {
+                resolved.insert(tileset.firstgid + id, ResolvedTile { index: id, solid: None });
+            }
+        }
+
+        // Listed tiles may point elsewhere or override solidity
+        for tile in &tileset.tiles {
+            match resolve_tile(tile, sheet_index(tile.id), path, definitions)? {
+                Some(resolved_tile) => resolved.insert(tileset.firstgid + tile.id, resolved_tile),
+                None => resolved.remove(&(tileset.firstgid + tile.id))
+            };
+        }
+    }
+
+    // ===== LAYERS =====
+    let mut map = Map::named_after(path);
+    for (layer_index, tiled_layer) in tiled.layers.iter().enumerate() {
+        let mut layer = new_layer(tiled_layer, layer_index, path)?;
+
+        for y in 0..tiled.height {
+            let mut col_vec = Vec::new();
+
+            for x in 0..tiled.width {
+                let gid = tiled_layer.data[y * tiled.width + x] & !GID_FLAG_MASK;
+                if gid == 0 {
+                    col_vec.push(None);
+                    continue;
+                }
+                let tile = resolved.get(&gid).ok_or_else(|| MapLoadError::UnknownGid {
+                    path: path.to_path_buf(),
+                    layer: tiled_layer.name.clone(),
+                    x,
+                    y,
+                    gid
+                })?;
+
+                let mut pixel = Pixel::new(
+                    x as i32 * tile_size as i32,
+                    y as i32 * tile_size as i32,
+                    tile.index
+                );
+                pixel.solid = tile.solid;
+                col_vec.push(Some(pixel));
+            }
+
+            layer.map.push(col_vec);
+        }
+
+        map.layers.push(layer);
+    }
+
+    Ok(map)
+}
+
+/// Creates an empty layer from a Tiled layer's `kind` and `collision`
+/// custom properties. Without a `kind`, the first layer is ground and the
+/// rest are decoration.
+fn new_layer(tiled_layer: &TiledLayer, layer_index: usize, path: &Path) -> Result<MapLayer, MapLoadError> {
+    let kind = match tiled_layer.properties.get("kind") {
+        Some(TiledProperty::Text(name)) => LayerKind::from_name(name).ok_or_else(|| {
+            format_error(path, format!("layer '{}': '{}' is not a layer kind", tiled_layer.name, name))
+        })?,
+        Some(_) => {
+            return Err(format_error(path, format!("layer '{}': kind must be a string", tiled_layer.name)));
+        },
+        None if layer_index == 0 => LayerKind::Ground,
+        None => LayerKind::Decoration
+    };
+
+    let mut layer = MapLayer::new(tiled_layer.name.clone(), kind);
+    match tiled_layer.properties.get("collision") {
+        Some(TiledProperty::Bool(collision)) => layer.collision = *collision,
+        Some(_) => {
+            return Err(format_error(path, format!("layer '{}': collision must be a bool", tiled_layer.name)));
+        },
+        None => ()
+    }
+    Ok(layer)
+}
+
+/// Finds the manifest tile a Tiled tile stands for. `sheet_index` is the
+/// manifest tile it stands for by its place in an image tileset, if any.
+///
+/// Returns `Ok(None)` for tiles that cannot be matched; using one of them in
+/// a layer is reported as an unknown gid.
+fn resolve_tile(
+    tile: &TiledTile,
+    sheet_index: Option<u32>,
+    path: &Path,
+    definitions: &[TileDefinition]
+) -> Result<Option<ResolvedTile>, MapLoadError> {
+    let index = match tile.properties.get("tile") {
+        Some(TiledProperty::Int(id)) => {
+            let id = u32::try_from(*id).ok()
+                .filter(|id| (*id as usize) < definitions.len())
+                .ok_or_else(|| format_error(path, format!("tile property {} is not a tile id", id)))?;
+            Some(id)
+        },
+        Some(TiledProperty::Text(name)) => {
+            let definition = definitions.iter()
+                .find(|definition| &definition.name == name)
+                .ok_or_else(|| format_error(path, format!("tile property '{}' is not a tile name", name)))?;
+            Some(definition.id)
+        },
+        Some(TiledProperty::Bool(_)) => {
+            return Err(format_error(path, "tile property must be a tile name or id"));
+        },
+        None => tile.image.as_deref()
+            .and_then(|image| {
+                let file_name = Path::new(image).file_name()?;
+                definitions.iter()
+                    .find(|definition| definition.image.file_name() == Some(file_name))
+                    .map(|definition| definition.id)
+            })
+            .or(sheet_index)
+    };
+
+    let solid = match tile.properties.get("solid") {
+        Some(TiledProperty::Bool(solid)) => Some(*solid),
+        Some(_) => return Err(format_error(path, "solid property must be a bool")),
+        None => None
+    };
+
+    Ok(index.map(|index| ResolvedTile { index, solid }))
+}
+
+/// Decodes a layer's `data` field according to its encoding and compression.
+fn decode_layer_data(
+    text: &str,
+    encoding: &str,
+    compression: &str,
+    path: &Path
+) -> Result<Vec<u32>, MapLoadError> {
+    match encoding {
+        "csv" => text.split(',')
+            .map(|token| token.trim())
+            .filter(|token| !token.is_empty())
+            .map(|token| token.parse::<u32>()
+                .map_err(|_| format_error(path, format!("'{}' is not a gid", token))))
+            .collect(),
+        "base64" => {
+            let raw = base64::engine::general_purpose::STANDARD
+                .decode(text.trim())
+                .map_err(|e| format_error(path, format!("invalid base64 layer data: {}", e)))?;
+
+            let mut bytes = Vec::new();
+            let result = match compression {
+                "" => {
+                    bytes = raw;
+                    Ok(0)
+                },
+                "zlib" => ZlibDecoder::new(raw.as_slice()).read_to_end(&mut bytes),
+                "gzip" => GzDecoder::new(raw.as_slice()).read_to_end(&mut bytes),
+                other => {
+                    return Err(format_error(path, format!("unsupported layer compression '{}'", other)));
+                }
+            };
+            result.map_err(|e| format_error(path, format!("failed to decompress layer data: {}", e)))?;
+
+            if bytes.len() % 4 != 0 {
+                return Err(format_error(path, "base64 layer data is not a whole number of gids"));
+            }
+            Ok(bytes.chunks_exact(4)
+                .map(|gid| u32::from_le_bytes([gid[0], gid[1], gid[2], gid[3]]))
+                .collect())
+        },
+        other => Err(format_error(path, format!("unsupported layer encoding '{}'", other)))
+    }
+}
+
+/// Checks that a decoded layer covers the whole map.
+fn check_layer_size(layer: &TiledLayer, width: usize, height: usize, path: &Path) -> Result<(), MapLoadError> {
+    if layer.data.len() != width * height {
+        return Err(format_error(
+            path,
+            format!(
+                "layer '{}' has {} cells, expected {}x{}",
+                layer.name, layer.data.len(), width, height
+            )
+        ));
+    }
+    Ok(())
+}
+
+// ========== JSON (.tmj / .tsj) ==========
+
+#[derive(Deserialize)]
+struct TmjMap {
+    width: usize,
+    height: usize,
+    #[serde(default)]
+    infinite: bool,
+    #[serde(default)]
+    orientation: String,
+    #[serde(default)]
+    layers: Vec<TmjLayer>,
+    #[serde(default)]
+    tilesets: Vec<TmjTileset>
+}
+
+#[derive(Deserialize)]
+struct TmjLayer {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    data: Option<serde_json::Value>,
+    #[serde(default)]
+    encoding: Option<String>,
+    #[serde(default)]
+    compression: Option<String>,
+    #[serde(default)]
+    properties: Vec<TmjProperty>,
+    #[serde(default)]
+    layers: Vec<TmjLayer>
+}
+
+#[derive(Deserialize)]
+struct TmjTileset {
+    #[serde(default)]
+    firstgid: u32,
+    #[serde(default)]
+    source: Option<String>,
+    #[serde(default)]
+    image: Option<String>,
+    #[serde(default)]
+    tilecount: Option<u32>,
+    #[serde(default)]
+    tiles: Vec<TmjTile>
+}
+
+#[derive(Deserialize)]
+struct TmjTile {
+    id: u32,
+    #[serde(default)]
+    image: Option<String>,
+    #[serde(default)]
+    properties: Vec<TmjProperty>
+}
+
+#[derive(Deserialize)]
+struct TmjProperty {
+    name: String,
+    value: serde_json::Value
+}
+
+fn parse_tmj(source: &str, path: &Path) -> Result<TiledMap, MapLoadError> {
+    let tmj: TmjMap = serde_json::from_str(source)
+        .map_err(|e| format_error(path, e.to_string()))?;
+
+    if tmj.infinite {
+        return Err(format_error(path, "infinite maps are not supported"));
+    }
+    if !tmj.orientation.is_empty() && tmj.orientation != "orthogonal" {
+        return Err(format_error(path, format!("{} maps are not supported", tmj.orientation)));
+    }
+
+    let mut layers = Vec::new();
+    collect_tmj_layers(&tmj.layers, path, &mut layers)?;
+    for layer in &layers {
+        check_layer_size(layer, tmj.width, tmj.height, path)?;
+    }
+
+    let mut tilesets = Vec::new();
+    for tileset in tmj.tilesets {
+        tilesets.push(match tileset.source {
+            Some(source) => load_external_tileset(path, &source, tileset.firstgid)?,
+            None => tmj_tileset(tileset, path)?
+        });
+    }
+
+    Ok(TiledMap { width: tmj.width, height: tmj.height, tilesets, layers })
+}
+
+/// Collects tile layers in draw order, descending into group layers.
+fn collect_tmj_layers(
+    tmj_layers: &[TmjLayer],
+    path: &Path,
+    layers: &mut Vec<TiledLayer>
+) -> Result<(), MapLoadError> {
+    for layer in tmj_layers {
+        match layer.kind.as_str() {
+            "tilelayer" => {
+                let encoding = layer.encoding.as_deref().unwrap_or("csv");
+                let compression = layer.compression.as_deref().unwrap_or("");
+                let data = match &layer.data {
+                    Some(serde_json::Value::Array(values)) => values.iter()
+                        .map(|value| value.as_u64()
+                            .and_then(|gid| u32::try_from(gid).ok())
+                            .ok_or_else(|| format_error(path, format!("'{}' is not a gid", value))))
+                        .collect::<Result<Vec<u32>, MapLoadError>>()?,
+                    Some(serde_json::Value::String(text)) => {
+                        decode_layer_data(text, encoding, compression, path)?
+                    },
+                    _ => {
+                        return Err(format_error(path, format!("layer '{}' has no tile data", layer.name)));
+                    }
+                };
+                let properties = tmj_properties(&layer.properties, path)?;
+                layers.push(TiledLayer { name: layer.name.clone(), properties, data });
+            },
+            "group" => collect_tmj_layers(&layer.layers, path, layers)?,
+            // Object and image layers carry nothing we can place in the grid
+            _ => ()
+        }
+    }
+    Ok(())
+}
+
+fn tmj_tileset(tileset: TmjTileset, path: &Path) -> Result<TiledTileset, MapLoadError> {
+    let mut tiles = Vec::new();
+    for tile in tileset.tiles {
+        let properties = tmj_properties(&tile.properties, path)?;
+        tiles.push(TiledTile { id: tile.id, image: tile.image, properties });
+    }
+    let tilecount = sheet_tilecount(tileset.image.as_deref(), tileset.tilecount, path)?;
+    Ok(TiledTileset { firstgid: tileset.firstgid, image: tileset.image, tilecount, tiles })
+}
+
+/// Number of tiles cut from an image tileset's sheet, which Tiled always
+/// records. Collections of images have no sheet and cut nothing.
+fn sheet_tilecount(image: Option<&str>, tilecount: Option<u32>, path: &Path) -> Result<u32, MapLoadError> {
+    match (image, tilecount) {
+        (Some(image), None) => Err(format_error(path, format!("tileset image '{}' has no tilecount", image))),
+        (Some(_), Some(tilecount)) => Ok(tilecount),
+        (None, _) => Ok(0)
+    }
+}
+
+fn tmj_properties(tmj_properties: &[TmjProperty], path: &Path) -> Result<HashMap<String, TiledProperty>, MapLoadError> {
+    let mut properties = HashMap::new();
+    for property in tmj_properties {
+        let value = match &property.value {
+            serde_json::Value::Bool(value) => TiledProperty::Bool(*value),
+            serde_json::Value::Number(value) => TiledProperty::Int(
+                value.as_i64()
+                    .ok_or_else(|| format_error(path, format!("property '{}' is not an integer", property.name)))?
+            ),
+            serde_json::Value::String(value) => TiledProperty::Text(value.clone()),
+            _ => continue
+        };
+        properties.insert(property.name.clone(), value);
+    }
+    Ok(properties)
+}
+
+/// Loads a tileset stored in its own `.tsj`/`.tsx` file next to the map.
+fn load_external_tileset(map_path: &Path, source: &str, firstgid: u32) -> Result<TiledTileset, MapLoadError> {
+    let path: PathBuf = map_path.parent().unwrap_or(Path::new(".")).join(source);
+    let text = read_source(&path)?;
+
+    let mut tileset = match extension(&path).as_str() {
+        "tsx" => {
+            let root = parse_xml(&text, &path)?;
+            if root.name != "tileset" {
+                return Err(format_error(&path, "expected a <tileset> root element"));
+            }
+            tmx_tileset(&root, &path)?
+        },
+        _ => {
+            let tmj: TmjTileset = serde_json::from_str(&text)
+                .map_err(|e| format_error(&path, e.to_string()))?;
+            tmj_tileset(tmj, &path)?
+        }
+    };
+    tileset.firstgid = firstgid;
+    Ok(tileset)
+}
+
+// ========== XML (.tmx / .tsx) ==========
+
+/// Minimal element tree built from the XML event stream.
+struct XmlElement {
+    name: String,
+    attributes: HashMap<String, String>,
+    children: Vec<XmlElement>,
+    text: String
+}
+
+impl XmlElement {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(|value| value.as_str())
+    }
+
+    fn children_named<'e>(&'e self, name: &'e str) -> impl Iterator<Item = &'e XmlElement> + 'e {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    fn parse_attribute<T: std::str::FromStr>(&self, name: &str, path: &Path) -> Result<Option<T>, MapLoadError> {
+        self.attribute(name)
+            .map(|value| value.parse().map_err(|_| {
+                format_error(path, format!("<{}> attribute {}='{}' is invalid", self.name, name, value))
+            }))
+            .transpose()
+    }
+}
+
+fn xml_element(start: &BytesStart, path: &Path) -> Result<XmlElement, MapLoadError> {
+    let mut attributes = HashMap::new();
+    for attribute in start.attributes() {
+        let attribute = attribute.map_err(|e| format_error(path, e.to_string()))?;
+        let key = String::from_utf8_lossy(attribute.key.as_ref()).into_owned();
+        let value = attribute.unescape_value()
+            .map_err(|e| format_error(path, e.to_string()))?
+            .into_owned();
+        attributes.insert(key, value);
+    }
+    Ok(XmlElement {
+        name: String::from_utf8_lossy(start.name().as_ref()).into_owned(),
+        attributes,
+        children: Vec::new(),
+        text: String::new()
+    })
+}
+
+fn parse_xml(source: &str, path: &Path) -> Result<XmlElement, MapLoadError> {
+    let mut reader = quick_xml::Reader::from_str(source);
+    let mut stack: Vec<XmlElement> = Vec::new();
+
+    loop {
+        let event = reader.read_event().map_err(|e| {
+            format_error(path, format!("at byte {}: {}", reader.buffer_position(), e))
+        })?;
+        match event {
+            Event::Start(start) => stack.push(xml_element(&start, path)?),
+            Event::Empty(start) => {
+                let element = xml_element(&start, path)?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Ok(element)
+                }
+            },
+            Event::Text(text) => {
+                if let Some(element) = stack.last_mut() {
+                    let text = text.unescape().map_err(|e| format_error(path, e.to_string()))?;
+                    element.text.push_str(&text);
+                }
+            },
+            Event::CData(text) => {
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&String::from_utf8_lossy(&text));
+                }
+            },
+            Event::End(_) => {
+                let element = stack.pop().ok_or_else(|| format_error(path, "unbalanced closing tag"))?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Ok(element)
+                }
+            },
+            Event::Eof => return Err(format_error(path, "unexpected end of file")),
+            _ => ()
+        }
+    }
+}
+
+fn parse_tmx(source: &str, path: &Path) -> Result<TiledMap, MapLoadError> {
+    let root = parse_xml(source, path)?;
+    if root.name != "map" {
+        return Err(format_error(path, "expected a <map> root element"));
+    }
+    if root.attribute("infinite") == Some("1") {
+        return Err(format_error(path, "infinite maps are not supported"));
+    }
+    if let Some(orientation) = root.attribute("orientation") && orientation != "orthogonal" {
+        return Err(format_error(path, format!("{} maps are not supported", orientation)));
+    }
+
+    let width: usize = root.parse_attribute("width", path)?
+        .ok_or_else(|| format_error(path, "<map> has no width"))?;
+    let height: usize = root.parse_attribute("height", path)?
+        .ok_or_else(|| format_error(path, "<map> has no height"))?;
+
+    let mut tilesets = Vec::new();
+    for tileset in root.children_named("tileset") {
+        let firstgid = tileset.parse_attribute("firstgid", path)?.unwrap_or(1);
+        tilesets.push(match tileset.attribute("source") {
+            Some(source) => load_external_tileset(path, source, firstgid)?,
+            None => {
+                let mut tileset = tmx_tileset(tileset, path)?;
+                tileset.firstgid = firstgid;
+                tileset
+            }
+        });
+    }
+
+    let mut layers = Vec::new();
+    collect_tmx_layers(&root, path, &mut layers)?;
+    for layer in &layers {
+        check_layer_size(layer, width, height, path)?;
+    }
+
+    Ok(TiledMap { width, height, tilesets, layers })
+}
+
+/// Collects `<layer>` elements in draw order, descending into `<group>`s.
+fn collect_tmx_layers(parent: &XmlElement, path: &Path, layers: &mut Vec<TiledLayer>) -> Result<(), MapLoadError> {
+    for child in &parent.children {
+        match child.name.as_str() {
+            "layer" => {
+                let name = child.attribute("name").unwrap_or("").to_string();
+                let data = child.children_named("data").next()
+                    .ok_or_else(|| format_error(path, format!("layer '{}' has no <data>", name)))?;
+                let encoding = data.attribute("encoding").ok_or_else(|| {
+                    format_error(path, format!("layer '{}' uses XML tile data; save it as CSV or base64", name))
+                })?;
+                if data.children_named("chunk").next().is_some() {
+                    return Err(format_error(path, "infinite maps are not supported"));
+                }
+                let compression = data.attribute("compression").unwrap_or("");
+                let data = decode_layer_data(&data.text, encoding, compression, path)?;
+                let properties = tmx_properties(child, path)?;
+                layers.push(TiledLayer { name, properties, data });
+            },
+            "group" => collect_tmx_layers(child, path, layers)?,
+            _ => ()
+        }
+    }
+    Ok(())
+}
+
+fn tmx_tileset(tileset: &XmlElement, path: &Path) -> Result<TiledTileset, MapLoadError> {
+    let mut tiles = Vec::new();
+    for tile in tileset.children_named("tile") {
+        let id = tile.parse_attribute("id", path)?
+            .ok_or_else(|| format_error(path, "<tile> has no id"))?;
+        let image = tile.children_named("image").next()
+            .and_then(|image| image.attribute("source"))
+            .map(|source| source.to_string());
+
+        let properties = tmx_properties(tile, path)?;
+
+        tiles.push(TiledTile { id, image, properties });
+    }
+
+    let image = tileset.children_named("image").next()
+        .and_then(|image| image.attribute("source"))
+        .map(|source| source.to_string());
+    let tilecount = sheet_tilecount(image.as_deref(), tileset.parse_attribute("tilecount", path)?, path)?;
+    Ok(TiledTileset { firstgid: 1, image, tilecount, tiles })
+}
+
+/// Reads the `<properties>` block of a tile or layer element.
+fn tmx_properties(element: &XmlElement, path: &Path) -> Result<HashMap<String, TiledProperty>, MapLoadError> {
+    let mut properties = HashMap::new();
+    for property in element.children_named("properties").flat_map(|p| p.children_named("property")) {
+        let name = property.attribute("name")
+            .ok_or_else(|| format_error(path, "<property> has no name"))?;
+        let value = property.attribute("value").unwrap_or(&property.text);
+        let value = match property.attribute("type").unwrap_or("string") {
+            "bool" => TiledProperty::Bool(value == "true"),
+            "int" => TiledProperty::Int(value.parse().map_err(|_| {
+                format_error(path, format!("property '{}' is not an integer", name))
+            })?),
+            _ => TiledProperty::Text(value.to_string())
+        };
+        properties.insert(name.to_string(), value);
+    }
+    Ok(properties)
+}
@@ -0,0 +1,65 @@
+use std::{
+    thread,
+    time::{Duration, Instant}
+};
+
+/// Paces a fixed-timestep game loop.
+///
+/// The game is updated in whole ticks of `1 / tick_rate` seconds while
+/// rendering runs as often as the loop does. Each frame `advance` says how
+/// many ticks are due, and `alpha` how far the clock is into the next one so
+/// rendering can interpolate between the previous and current state.
+pub struct FixedTimestep {
+    tick: Duration,
+    max_updates_per_frame: u32,     // Catch-up cap, so a slow frame cannot snowball
+    accumulator: Duration,          // Time not yet consumed by updates
+    last_time: Instant
+}
+
+impl FixedTimestep {
+    /// # Arguments
+    /// * `tick_rate` - Updates per second
+    /// * `max_updates_per_frame` - Most ticks run in one frame when catching up
+    pub fn new(tick_rate: u32, max_updates_per_frame: u32) -> Self {
+        Self {
+            tick: Duration::from_secs(1) / tick_rate.max(1),
+            max_updates_per_frame: max_updates_per_frame.max(1),
+            accumulator: Duration::ZERO,
+            last_time: Instant::now()
+        }
+    }
+
+    /// Adds the time elapsed since the previous call and returns how many
+    /// ticks to run this frame.
+    ///
+    /// When more than `max_updates_per_frame` ticks are due (after a stall,
+    /// or when updates are slower than real time) the excess is dropped and
+    /// the game slows down instead of falling further behind every frame.
+    pub fn advance(&mut self) -> u32 {
+        let now = Instant::now();
+        self.accumulator += now - self.last_time;
+        self.last_time = now;
+
+        let due = (self.accumulator.as_nanos() / self.tick.as_nanos()) as u32;
+        self.accumulator -= self.tick * due;
+        due.min(self.max_updates_per_frame)
+    }
+
+    /// Game time covered by one tick.
+    pub fn tick(&self) -> Duration {
+        self.tick
+    }
+
+    /// How far into the next tick the clock is, from 0.0 to 1.0.
+    pub fn alpha(&self) -> f32 {
+        self.accumulator.as_secs_f32() / self.tick.as_secs_f32()
+    }
+
+    /// Sleeps until the next tick is due instead of spinning on the clock.
+    pub fn sleep_until_next_tick(&self) {
+        let waited = self.accumulator + self.last_time.elapsed();
+        if let Some(remaining) = self.tick.checked_sub(waited) {
+            thread::sleep(remaining);
+        }
+    }
+}
@@ -0,0 +1,326 @@
+extern crate sdl3;
+
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf}
+};
+
+use sdl3::{
+    pixels::Color,
+    render::{BlendMode, Canvas, FRect},
+    video::Window
+};
+use serde::Deserialize;
+
+use crate::{
+    assets::asset_manager::AssetManager,
+    ui::font::{Font, TextStyle}
+};
+
+/// Default location of the dialogue files. Every `.toml` file in it is loaded.
+pub const DIALOGUE_DIR: &str = "res/dialogue";
+
+// Text capacity of the box, in characters of a monospaced font
+const COLUMNS: usize = 44;
+const ROWS: usize = 3;
+
+// Box layout in screen pixels. Characters are drawn at twice their size
+const TEXT_SCALE: f32 = 2.0;
+const LINE_SPACING: u32 = 4;        // Extra gap between lines of text
+const PADDING: u32 = 16;
+const BORDER: u32 = 4;
+const MARGIN: u32 = 16;             // Gap between the box and the bottom of the screen
+
+/// Ticks spent revealing each character (30 characters per second at 60 ticks/s).
+const TICKS_PER_CHAR: u32 = 2;
+
+/// Ticks the "more" marker stays on, then off, while waiting for confirm.
+const BLINK_TICKS: u32 = 30;
+
+// ========== FILE FORMAT ==========
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DialogueFile {
+    #[serde(default)]
+    dialogue: HashMap<String, DialogueDefinition>
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DialogueDefinition {
+    speaker: Option<String>,
+    text: Vec<String>,          // Each entry starts a new page, and runs over more if long
+    sets_flag: Option<String>   // Story flag raised once the dialogue is finished
+}
+
+/// Everything that can go wrong while reading dialogue files.
+#[derive(Debug)]
+pub enum DialogueLoadError {
+    Io { path: PathBuf, message: String },
+    Parse { path: PathBuf, message: String },
+    DuplicateId { path: PathBuf, id: String },
+    EmptyText { path: PathBuf, id: String }
+}
+
+impl fmt::Display for DialogueLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DialogueLoadError::Io { path, message } => {
+                write!(f, "{}: failed to read dialogue: {}", path.display(), message)
+            },
+            DialogueLoadError::Parse { path, message } => {
+                write!(f, "{}: invalid dialogue: {}", path.display(), message)
+            },
+            DialogueLoadError::DuplicateId { path, id } => {
+                write!(f, "{}: dialogue '{}' is defined more than once", path.display(), id)
+            },
+            DialogueLoadError::EmptyText { path, id } => {
+                write!(f, "{}: dialogue '{}' has no text", path.display(), id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DialogueLoadError {}
+
+// ========== DIALOGUE LIBRARY ==========
+
+/// One conversation as written in a dialogue file.
+pub struct DialogueEntry {
+    pub speaker: Option<String>,
+    pub text: Vec<String>,
+    pub sets_flag: Option<String>   // Story flag raised once the dialogue is finished
+}
+
+/// Every dialogue in the game, by id.
+pub struct DialogueLibrary {
+    entries: HashMap<String, DialogueEntry>
+}
+
+impl DialogueLibrary {
+    /// Loads every `.toml` file in `dir`. Ids must be unique across files.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Self, DialogueLoadError> {
+        let dir = dir.as_ref();
+        let io_error = |e: std::io::Error| DialogueLoadError::Io {
+            path: dir.to_path_buf(),
+            message: e.to_string()
+        };
+
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir).map_err(io_error)? {
+            let path = entry.map_err(io_error)?.path();
+            if path.is_file() && path.extension().is_some_and(|e| e == "toml") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut entries = HashMap::new();
+        for path in paths {
+            let source = fs::read_to_string(&path).map_err(|e| DialogueLoadError::Io {
+                path: path.clone(),
+                message: e.to_string()
+            })?;
+            let file: DialogueFile = toml::from_str(&source).map_err(|e| DialogueLoadError::Parse {
+                path: path.clone(),
+                message: e.to_string()
+            })?;
+
+            for (id, definition) in file.dialogue {
+                if definition.text.iter().all(|text| text.trim().is_empty()) {
+                    return Err(DialogueLoadError::EmptyText { path, id });
+                }
+                if entries.contains_key(&id) {
+                    return Err(DialogueLoadError::DuplicateId { path, id });
+                }
+                entries.insert(id, DialogueEntry {
+                    speaker: definition.speaker,
+                    text: definition.text,
+                    sets_flag: definition.sets_flag
+                });
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// The dialogue called `id`.
+    pub fn get(&self, id: &str) -> Option<&DialogueEntry> {
+        self.entries.get(id)
+    }
+}
+
+// ========== DIALOGUE BOX ==========
+
+/// An open dialogue box at the bottom of the screen.
+///
+/// Text is revealed one character at a time. Confirming while a page is
+/// still being revealed shows all of it; confirming a complete page moves
+/// to the next one, and after the last page the dialogue is finished.
+pub struct Dialogue {
+    speaker: Option<String>,
+    pages: Vec<Vec<String>>,    // Lines of each page, wrapped to fit the box
+    page: usize,
+    revealed: usize,            // Characters of the current page shown so far
+    ticks: u32,                 // Ticks since the page was opened
+    finished: bool,
+    sets_flag: Option<String>   // Story flag raised once finished
+}
+
+impl Dialogue {
+    /// Opens `entry`, wrapping its text into pages that fit the box.
+    pub fn new(entry: &DialogueEntry) -> Self {
+        let pages = entry.text.iter()
+            .flat_map(|text| paginate(text, COLUMNS, ROWS))
+            .collect();
+        Self {
+            speaker: entry.speaker.clone(),
+            pages,
+            page: 0,
+            revealed: 0,
+            ticks: 0,
+            finished: false,
+            sets_flag: entry.sets_flag.clone()
+        }
+    }
+
+    /// Story flag to raise once the dialogue is finished, if any.
+    pub fn sets_flag(&self) -> Option<&str> {
+        self.sets_flag.as_deref()
+    }
+
+    /// Whether the last page has been confirmed, so the box should close.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn page_length(&self) -> usize {
+        self.pages[self.page].iter().map(|line| line.chars().count()).sum()
+    }
+
+    /// Whether every character of the current page is shown.
+    pub fn is_page_complete(&self) -> bool {
+        self.revealed >= self.page_length()
+    }
+
+    /// Advances the dialogue by one tick.
+    ///
+    /// # Arguments
+    /// * `confirm` - Whether the confirm action was pressed this tick
+    pub fn update(&mut self, confirm: bool) {
+        if self.finished {
+            return;
+        }
+        self.ticks += 1;
+
+        if !confirm {
+            self.revealed = (self.ticks / TICKS_PER_CHAR) as usize;
+        } else if !self.is_page_complete() {
+            // Skip the rest of the typewriter effect
+            self.revealed = self.page_length();
+            self.ticks = self.revealed as u32 * TICKS_PER_CHAR;
+        } else if self.page + 1 < self.pages.len() {
+            self.page += 1;
+            self.revealed = 0;
+            self.ticks = 0;
+        } else {
+            self.finished = true;
+        }
+    }
+
+    /// Draws the box, the speaker's name and the revealed text. The box is
+    /// sized to fit `COLUMNS` characters of `font`, which should be monospaced.
+    ///
+    /// # Arguments
+    /// * `canvas` - SDL canvas to draw on
+    /// * `assets` - Holds the font's glyph sheet
+    /// * `font` - Font the text is drawn in
+    /// * `screen_width`, `screen_height` - Size of the screen the box sits at the bottom of
+    pub fn draw(
+        &self,
+        canvas: &mut Canvas<Window>,
+        assets: &mut AssetManager,
+        font: &mut Font,
+        screen_width: u32,
+        screen_height: u32
+    ) {
+        let char_width = font.measure("M", TEXT_SCALE);
+        let char_height = font.line_height(TEXT_SCALE);
+        let line_height = char_height + LINE_SPACING;
+        let width = COLUMNS as u32 * char_width + 2 * PADDING;
+        let height = (ROWS as u32 + 1) * line_height - LINE_SPACING + 2 * PADDING;
+        let x = screen_width.saturating_sub(width) / 2;
+        let y = screen_height.saturating_sub(height + MARGIN);
+
+        // ===== BOX =====
+        // Translucent background inside a solid border
+        canvas.set_blend_mode(BlendMode::Blend);
+        canvas.set_draw_color(Color::RGB(255, 255, 255));
+        canvas.fill_rect(FRect::new(x as f32, y as f32, width as f32, height as f32)).ok();
+        canvas.set_draw_color(Color::RGBA(20, 20, 40, 230));
+        canvas.fill_rect(FRect::new(
+            (x + BORDER) as f32,
+            (y + BORDER) as f32,
+            (width - 2 * BORDER) as f32,
+            (height - 2 * BORDER) as f32
+        )).ok();
+
+        // ===== TEXT =====
+        let text_x = (x + PADDING) as i32;
+        let line_y = |row: u32| (y + PADDING + row * line_height) as i32;
+
+        if let Some(speaker) = &self.speaker {
+            let style = TextStyle { color: Color::RGB(255, 220, 100), scale: TEXT_SCALE, ..TextStyle::default() };
+            font.draw(canvas, assets, speaker, text_x, line_y(0), &style);
+        }
+
+        let style = TextStyle { scale: TEXT_SCALE, ..TextStyle::default() };
+        let mut remaining = self.revealed;
+        for (row, line) in self.pages[self.page].iter().enumerate() {
+            let shown: String = line.chars().take(remaining).collect();
+            remaining -= shown.chars().count();
+            font.draw(canvas, assets, &shown, text_x, line_y(row as u32 + 1), &style);
+        }
+
+        // ===== MORE MARKER =====
+        // Blinks in the bottom right corner while waiting for confirm
+        if self.is_page_complete() && (self.ticks / BLINK_TICKS).is_multiple_of(2) {
+            let size = char_width / 2;
+            canvas.set_draw_color(Color::RGB(255, 255, 255));
+            canvas.fill_rect(FRect::new(
+                (x + width - PADDING - size) as f32,
+                (y + height - PADDING - size) as f32,
+                size as f32,
+                size as f32
+            )).ok();
+        }
+    }
+}
+
+/// Word-wraps `text` to `columns` characters and groups the lines into
+/// pages of `rows` lines. Words longer than a line are split.
+fn paginate(text: &str, columns: usize, rows: usize) -> Vec<Vec<String>> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        let length = line.chars().count();
+        if length > 0 && length + 1 + word.len() > columns {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        while word.len() > columns {
+            lines.push(word.drain(..columns).collect());
+        }
+        line.extend(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+
+    lines.chunks(rows).map(|page| page.to_vec()).collect()
+}
@@ -0,0 +1,337 @@
+extern crate sdl3;
+
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf}
+};
+
+use sdl3::{
+    pixels::Color,
+    rect::Rect,
+    render::{Canvas, FRect, Texture, TextureCreator},
+    ttf::{self, Sdl3TtfContext},
+    video::{Window, WindowContext}
+};
+use serde::Deserialize;
+
+use crate::assets::asset_manager::{AssetManager, TextureHandle};
+
+/// Bitmap font used for dialogue and other in-game text.
+pub const DEFAULT_FONT_PATH: &str = "res/fonts/mono.toml";
+
+/// Outline font for titles and the development overlay.
+pub const TITLE_FONT_PATH: &str = "res/fonts/DejaVuSans-Bold.ttf";
+
+/// Character drawn in place of those a bitmap font lacks.
+const FALLBACK_CHAR: char = '?';
+
+// ========== FILE FORMAT ==========
+
+/// On-disk layout of a bitmap font's metadata file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BitmapFontFile {
+    image: PathBuf,             // Glyph sheet, relative to the metadata file
+    glyph_width: u32,
+    glyph_height: u32,
+    columns: u32,               // Glyphs per row of the sheet
+    first: u32,                 // Character code of the first glyph
+    count: u32                  // Number of glyphs in the sheet
+}
+
+/// Everything that can go wrong while loading a font.
+#[derive(Debug)]
+pub enum FontError {
+    Io { path: PathBuf, message: String },
+    Parse { path: PathBuf, message: String },
+    InvalidLayout { path: PathBuf },
+    Ttf { path: PathBuf, message: String }
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FontError::Io { path, message } => {
+                write!(f, "{}: failed to read font: {}", path.display(), message)
+            },
+            FontError::Parse { path, message } => {
+                write!(f, "{}: invalid font: {}", path.display(), message)
+            },
+            FontError::InvalidLayout { path } => {
+                write!(f, "{}: glyph size, columns and count must all be above 0", path.display())
+            },
+            FontError::Ttf { path, message } => {
+                write!(f, "{}: failed to load TrueType font: {}", path.display(), message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FontError {}
+
+// ========== STYLE ==========
+
+/// Horizontal placement of each line of text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Align {
+    Left,       // Lines start at the given X (or the left of the area)
+    Center,     // Lines are centred on the given X (or in the area)
+    Right       // Lines end at the given X (or the right of the area)
+}
+
+/// How text is drawn.
+#[derive(Clone, Copy, Debug)]
+pub struct TextStyle {
+    pub color: Color,
+    pub scale: f32,             // Multiplies the font's pixel size, e.g. 2.0 for chunky pixel text
+    pub align: Align
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        Self { color: Color::RGB(255, 255, 255), scale: 1.0, align: Align::Left }
+    }
+}
+
+// ========== FONTS ==========
+
+/// A fixed-size font drawn from a sheet of glyphs. Every glyph's source
+/// rectangle is worked out up front, so drawing is a texture copy per character.
+pub struct BitmapFont {
+    texture: TextureHandle,
+    glyph_width: u32,
+    glyph_height: u32,
+    glyphs: HashMap<char, Rect>     // Source rectangle of each character in the sheet
+}
+
+/// A glyph of a TrueType font, rendered once in white and tinted when drawn.
+struct CachedGlyph<'a> {
+    texture: Texture<'a>,
+    width: u32,
+    height: u32
+}
+
+/// A TrueType font rendered through SDL_ttf.
+///
+/// Each character is rendered to its own texture the first time it is
+/// drawn and reused afterwards, so redrawing text every frame costs no
+/// more than a bitmap font. Kerning is not applied between cached glyphs.
+pub struct TtfFont<'a> {
+    font: ttf::Font<'static>,
+    texture_creator: &'a TextureCreator<WindowContext>,
+    glyphs: HashMap<char, Option<CachedGlyph<'a>>>      // `None` for characters that failed to render
+}
+
+/// Either kind of font, drawn and measured the same way.
+pub enum Font<'a> {
+    Bitmap(BitmapFont),
+    Ttf(TtfFont<'a>)
+}
+
+impl BitmapFont {
+    /// Loads a bitmap font's metadata and its glyph sheet.
+    ///
+    /// # Arguments
+    /// * `path` - Font metadata file
+    /// * `assets` - Loads the glyph sheet texture
+    pub fn load(path: impl AsRef<Path>, assets: &mut AssetManager) -> Result<Self, FontError> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path).map_err(|e| FontError::Io {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+        let file: BitmapFontFile = toml::from_str(&source).map_err(|e| FontError::Parse {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+        if file.glyph_width == 0 || file.glyph_height == 0 || file.columns == 0 || file.count == 0 {
+            return Err(FontError::InvalidLayout { path: path.to_path_buf() });
+        }
+
+        let glyphs = (0..file.count)
+            .filter_map(|index| {
+                let ch = char::from_u32(file.first + index)?;
+                let x = (index % file.columns * file.glyph_width) as i32;
+                let y = (index / file.columns * file.glyph_height) as i32;
+                Some((ch, Rect::new(x, y, file.glyph_width, file.glyph_height)))
+            })
+            .collect();
+
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        let texture = assets.load_texture(base_dir.join(&file.image));
+        Ok(Self { texture, glyph_width: file.glyph_width, glyph_height: file.glyph_height, glyphs })
+    }
+
+    fn glyph(&self, ch: char) -> Option<Rect> {
+        self.glyphs.get(&ch).or_else(|| self.glyphs.get(&FALLBACK_CHAR)).copied()
+    }
+}
+
+impl<'a> TtfFont<'a> {
+    /// Opens a TrueType font.
+    ///
+    /// # Arguments
+    /// * `context` - Initialised SDL_ttf library
+    /// * `path` - `.ttf` or `.otf` file
+    /// * `point_size` - Size to render glyphs at, before any `TextStyle::scale`
+    /// * `texture_creator` - Creates the cached glyph textures
+    pub fn load(
+        context: &Sdl3TtfContext,
+        path: impl AsRef<Path>,
+        point_size: f32,
+        texture_creator: &'a TextureCreator<WindowContext>
+    ) -> Result<Self, FontError> {
+        let path = path.as_ref();
+        let font = context.load_font(path, point_size).map_err(|e| FontError::Ttf {
+            path: path.to_path_buf(),
+            message: e.to_string()
+        })?;
+        Ok(Self { font, texture_creator, glyphs: HashMap::new() })
+    }
+
+    /// How far the pen moves after `ch` at a scale of 1. Used both to
+    /// measure and to draw text, so the two always agree.
+    fn advance(&self, ch: char) -> u32 {
+        self.font.size_of_char(ch).map(|(width, _)| width).unwrap_or(0)
+    }
+
+    /// Renders `ch` on first use; afterwards returns the cached glyph.
+    fn glyph(&mut self, ch: char) -> Option<&mut CachedGlyph<'a>> {
+        let font = &self.font;
+        let texture_creator = self.texture_creator;
+        self.glyphs.entry(ch)
+            .or_insert_with(|| {
+                let surface = font.render_char(ch).blended(Color::RGB(255, 255, 255)).ok()?;
+                let (width, height) = (surface.width(), surface.height());
+                let texture = surface.as_texture(texture_creator).ok()?;
+                Some(CachedGlyph { texture, width, height })
+            })
+            .as_mut()
+    }
+}
+
+impl<'a> Font<'a> {
+    /// Distance between the tops of two lines at `scale`, in pixels.
+    pub fn line_height(&self, scale: f32) -> u32 {
+        let height = match self {
+            Font::Bitmap(font) => font.glyph_height,
+            Font::Ttf(font) => font.font.recommended_line_spacing().max(0) as u32
+        };
+        (height as f32 * scale).round() as u32
+    }
+
+    /// Horizontal advance of `ch` at a scale of 1.
+    fn advance(&self, ch: char) -> u32 {
+        match self {
+            Font::Bitmap(font) => font.glyph_width,
+            Font::Ttf(font) => font.advance(ch)
+        }
+    }
+
+    /// Width of `text` drawn on one line at `scale`, in pixels.
+    pub fn measure(&self, text: &str, scale: f32) -> u32 {
+        let width: u32 = text.chars().map(|ch| self.advance(ch)).sum();
+        (width as f32 * scale).round() as u32
+    }
+
+    /// Splits `text` into lines no wider than `max_width` at `scale`.
+    ///
+    /// Lines break between words, and at every `\n`. A word too long for a
+    /// line on its own is broken between characters.
+    pub fn wrap(&self, text: &str, max_width: u32, scale: f32) -> Vec<String> {
+        let mut lines = Vec::new();
+        for paragraph in text.split('\n') {
+            let mut line = String::new();
+            for word in paragraph.split_whitespace() {
+                let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+                if self.measure(&candidate, scale) <= max_width {
+                    line = candidate;
+                    continue;
+                }
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+
+                // Break the word itself if it still does not fit
+                for ch in word.chars() {
+                    line.push(ch);
+                    if line.chars().count() > 1 && self.measure(&line, scale) > max_width {
+                        line.pop();
+                        lines.push(std::mem::replace(&mut line, ch.to_string()));
+                    }
+                }
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// Draws `text` with its first line's top at `y`, placed horizontally
+    /// around `x` according to `style.align`. `\n` starts a new line.
+    pub fn draw(&mut self, canvas: &mut Canvas<Window>, assets: &mut AssetManager, text: &str, x: i32, y: i32, style: &TextStyle) {
+        let line_height = self.line_height(style.scale) as i32;
+        for (row, line) in text.split('\n').enumerate() {
+            let width = self.measure(line, style.scale) as i32;
+            let line_x = match style.align {
+                Align::Left => x,
+                Align::Center => x - width / 2,
+                Align::Right => x - width
+            };
+            self.draw_line(canvas, assets, line, line_x, y + row as i32 * line_height, style);
+        }
+    }
+
+    /// Draws `text` wrapped to the width of `area` and aligned within it.
+    /// Lines that would run past the bottom of `area` are left out.
+    pub fn draw_in(&mut self, canvas: &mut Canvas<Window>, assets: &mut AssetManager, text: &str, area: Rect, style: &TextStyle) {
+        let line_height = self.line_height(style.scale) as i32;
+        let max_lines = (area.height() as i32 / line_height.max(1)) as usize;
+        let x = match style.align {
+            Align::Left => area.left(),
+            Align::Center => area.left() + area.width() as i32 / 2,
+            Align::Right => area.right()
+        };
+
+        let lines = self.wrap(text, area.width(), style.scale);
+        let shown = lines[..lines.len().min(max_lines)].join("\n");
+        self.draw(canvas, assets, &shown, x, area.top(), style);
+    }
+
+    /// Copies each glyph of `line` to the canvas, tinted with `style.color`.
+    fn draw_line(&mut self, canvas: &mut Canvas<Window>, assets: &mut AssetManager, line: &str, x: i32, y: i32, style: &TextStyle) {
+        let color = style.color;
+        let mut pen_x = x as f32;
+        match self {
+            Font::Bitmap(font) => {
+                let texture = assets.texture_mut(font.texture);
+                texture.set_color_mod(color.r, color.g, color.b);
+                texture.set_alpha_mod(color.a);
+                let (width, height) = (font.glyph_width as f32 * style.scale, font.glyph_height as f32 * style.scale);
+
+                for ch in line.chars() {
+                    if let Some(source) = font.glyph(ch) {
+                        let dest = FRect::new(pen_x, y as f32, width, height);
+                        canvas.copy(texture, Some(FRect::from(source)), dest).ok();
+                    }
+                    pen_x += width;
+                }
+                texture.set_color_mod(255, 255, 255);
+                texture.set_alpha_mod(255);
+            },
+            Font::Ttf(font) => {
+                for ch in line.chars() {
+                    // Glyphs that cannot be rendered still take up their space
+                    let advance = font.advance(ch) as f32 * style.scale;
+                    if let Some(glyph) = font.glyph(ch) {
+                        glyph.texture.set_color_mod(color.r, color.g, color.b);
+                        glyph.texture.set_alpha_mod(color.a);
+                        let (width, height) = (glyph.width as f32 * style.scale, glyph.height as f32 * style.scale);
+                        canvas.copy(&glyph.texture, None, FRect::new(pen_x, y as f32, width, height)).ok();
+                    }
+                    pen_x += advance;
+                }
+            }
+        }
+    }
+}
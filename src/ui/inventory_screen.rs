@@ -0,0 +1,162 @@
+extern crate sdl3;
+
+use sdl3::{
+    pixels::Color,
+    rect::Rect,
+    render::{BlendMode, Canvas, FRect},
+    video::Window
+};
+
+use crate::{
+    assets::asset_manager::AssetManager,
+    events::key_handler::{Action, PressTracker},
+    ui::font::{Align, Font, TextStyle},
+    world::World
+};
+
+/// Slots per row of the grid.
+const COLUMNS: usize = 5;
+
+// Panel layout in screen pixels
+const PANEL_WIDTH: u32 = 480;
+const PADDING: u32 = 16;
+const BORDER: u32 = 4;
+const SLOT_SIZE: u32 = 48;
+const SLOT_GAP: u32 = 8;
+const SECTION_GAP: u32 = 12;        // Between the title, the grid and the details
+const TITLE_SCALE: f32 = 2.0;
+const DESCRIPTION_LINES: u32 = 3;
+
+/// The inventory grid, opened over the frozen world.
+///
+/// The movement actions move a cursor between slots, and the name and
+/// description of the item under it are shown below the grid.
+#[derive(Default)]
+pub struct InventoryScreen {
+    selected: usize             // Index of the slot under the cursor
+}
+
+impl InventoryScreen {
+    /// Opens the screen with the cursor on the first slot.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index of the slot under the cursor.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Moves the cursor for this tick's presses, stopping at the edges of
+    /// the grid.
+    ///
+    /// # Arguments
+    /// * `presses` - Actions pressed this tick
+    /// * `slot_count` - Number of slots in the inventory shown
+    pub fn update(&mut self, presses: &PressTracker, slot_count: usize) {
+        let column = self.selected % COLUMNS;
+        if presses.was_pressed(Action::MoveLeft) && column > 0 {
+            self.selected -= 1;
+        }
+        if presses.was_pressed(Action::MoveRight) && column + 1 < COLUMNS && self.selected + 1 < slot_count {
+            self.selected += 1;
+        }
+        if presses.was_pressed(Action::MoveUp) && self.selected >= COLUMNS {
+            self.selected -= COLUMNS;
+        }
+        if presses.was_pressed(Action::MoveDown) && self.selected + COLUMNS < slot_count {
+            self.selected += COLUMNS;
+        }
+    }
+
+    /// Draws the player's inventory in a panel in the middle of the screen.
+    ///
+    /// # Arguments
+    /// * `canvas` - SDL canvas to draw on
+    /// * `assets` - Holds the item icons and the font's glyph sheet
+    /// * `font` - Font the text is drawn in
+    /// * `world` - Provides the player's inventory and the item definitions
+    /// * `screen_width`, `screen_height` - Size of the screen the panel is centred on
+    pub fn draw(
+        &self,
+        canvas: &mut Canvas<Window>,
+        assets: &mut AssetManager,
+        font: &mut Font,
+        world: &World,
+        screen_width: u32,
+        screen_height: u32
+    ) {
+        let slots = world.player.inventory.slots();
+        let rows = slots.len().div_ceil(COLUMNS) as u32;
+        let grid_width = COLUMNS as u32 * (SLOT_SIZE + SLOT_GAP) - SLOT_GAP;
+        let grid_height = rows * (SLOT_SIZE + SLOT_GAP) - SLOT_GAP;
+        let title_height = font.line_height(TITLE_SCALE);
+        let text_height = font.line_height(1.0);
+        let height = 2 * PADDING + title_height + grid_height + 2 * SECTION_GAP
+            + title_height + DESCRIPTION_LINES * text_height;
+        let x = screen_width.saturating_sub(PANEL_WIDTH) / 2;
+        let y = screen_height.saturating_sub(height) / 2;
+
+        // ===== PANEL =====
+        // Same look as the dialogue box
+        canvas.set_blend_mode(BlendMode::Blend);
+        canvas.set_draw_color(Color::RGB(255, 255, 255));
+        canvas.fill_rect(FRect::new(x as f32, y as f32, PANEL_WIDTH as f32, height as f32)).ok();
+        canvas.set_draw_color(Color::RGBA(20, 20, 40, 230));
+        canvas.fill_rect(FRect::new(
+            (x + BORDER) as f32,
+            (y + BORDER) as f32,
+            (PANEL_WIDTH - 2 * BORDER) as f32,
+            (height - 2 * BORDER) as f32
+        )).ok();
+
+        let title_style = TextStyle { scale: TITLE_SCALE, align: Align::Center, ..TextStyle::default() };
+        font.draw(canvas, assets, "Inventory", (x + PANEL_WIDTH / 2) as i32, (y + PADDING) as i32, &title_style);
+
+        // ===== SLOTS =====
+        // Icons with their count in the corner; the cursor is a yellow frame
+        let grid_x = x + (PANEL_WIDTH - grid_width) / 2;
+        let grid_y = y + PADDING + title_height + SECTION_GAP;
+        let count_style = TextStyle { align: Align::Right, ..TextStyle::default() };
+        for (index, slot) in slots.iter().enumerate() {
+            let slot_x = grid_x + (index % COLUMNS) as u32 * (SLOT_SIZE + SLOT_GAP);
+            let slot_y = grid_y + (index / COLUMNS) as u32 * (SLOT_SIZE + SLOT_GAP);
+            let rect = Rect::new(slot_x as i32, slot_y as i32, SLOT_SIZE, SLOT_SIZE);
+
+            canvas.set_draw_color(Color::RGBA(255, 255, 255, 40));
+            canvas.fill_rect(FRect::from(rect)).ok();
+            if index == self.selected {
+                canvas.set_draw_color(Color::RGB(255, 220, 100));
+                canvas.draw_rect(FRect::from(rect)).ok();
+            }
+
+            if let Some(stack) = slot {
+                world.items.draw_icon(canvas, assets, &stack.item, rect);
+                if stack.count > 1 {
+                    let count_y = rect.bottom() - text_height as i32;
+                    font.draw(canvas, assets, &stack.count.to_string(), rect.right() - 2, count_y, &count_style);
+                }
+            }
+        }
+
+        // ===== DETAILS =====
+        // Name and description of the item under the cursor
+        let item = slots.get(self.selected)
+            .and_then(|slot| slot.as_ref())
+            .and_then(|stack| world.items.get(&stack.item));
+        if let Some(item) = item {
+            let details_x = x + PADDING;
+            let details_y = grid_y + grid_height + SECTION_GAP;
+            let name_style = TextStyle { color: Color::RGB(255, 220, 100), scale: TITLE_SCALE, ..TextStyle::default() };
+            font.draw(canvas, assets, &item.name, details_x as i32, details_y as i32, &name_style);
+
+            let area = Rect::new(
+                details_x as i32,
+                (details_y + title_height) as i32,
+                PANEL_WIDTH - 2 * PADDING,
+                DESCRIPTION_LINES * text_height
+            );
+            font.draw_in(canvas, assets, &item.description, area, &TextStyle::default());
+        }
+    }
+}
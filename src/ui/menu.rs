@@ -0,0 +1,86 @@
+extern crate sdl3;
+
+use sdl3::{
+    pixels::Color,
+    render::Canvas,
+    video::Window
+};
+
+use crate::{
+    assets::asset_manager::AssetManager,
+    events::key_handler::{Action, PressTracker},
+    ui::font::{Align, Font, TextStyle}
+};
+
+// Entries are drawn in the text font at twice its size
+const TEXT_SCALE: f32 = 2.0;
+const LINE_SPACING: u32 = 8;
+
+/// A vertical list of entries with a cursor. Up and down move the cursor,
+/// wrapping around, and interact chooses the entry under it.
+pub struct Menu<T: Copy> {
+    entries: Vec<(String, T)>,      // Label and what choosing the entry means
+    selected: usize
+}
+
+impl<T: Copy> Menu<T> {
+    /// Creates a menu with the cursor on the first of `entries`.
+    pub fn new(entries: Vec<(String, T)>) -> Self {
+        Self { entries, selected: 0 }
+    }
+
+    /// What the entry under the cursor stands for.
+    pub fn selected(&self) -> Option<T> {
+        self.entries.get(self.selected).map(|(_, value)| *value)
+    }
+
+    /// Replaces every label with `label` of its entry's value, e.g. after
+    /// a value shown in the labels has changed.
+    pub fn relabel(&mut self, label: impl Fn(T) -> String) {
+        for (text, value) in &mut self.entries {
+            *text = label(*value);
+        }
+    }
+
+    /// Moves the cursor for this tick's presses.
+    ///
+    /// # Returns
+    /// The entry chosen this tick, if any
+    pub fn update(&mut self, presses: &PressTracker) -> Option<T> {
+        let count = self.entries.len();
+        if count == 0 {
+            return None;
+        }
+        if presses.was_pressed(Action::MoveUp) {
+            self.selected = (self.selected + count - 1) % count;
+        }
+        if presses.was_pressed(Action::MoveDown) {
+            self.selected = (self.selected + 1) % count;
+        }
+        if presses.was_pressed(Action::Interact) {
+            return self.selected();
+        }
+        None
+    }
+
+    /// Height of the drawn menu in pixels.
+    pub fn height(&self, font: &Font) -> u32 {
+        let line_height = font.line_height(TEXT_SCALE) + LINE_SPACING;
+        (self.entries.len() as u32 * line_height).saturating_sub(LINE_SPACING)
+    }
+
+    /// Draws the entries centred on `center_x`, starting at `top`. The
+    /// entry under the cursor is highlighted.
+    pub fn draw(&self, canvas: &mut Canvas<Window>, assets: &mut AssetManager, font: &mut Font, center_x: i32, top: i32) {
+        let line_height = (font.line_height(TEXT_SCALE) + LINE_SPACING) as i32;
+        for (index, (label, _)) in self.entries.iter().enumerate() {
+            let (text, color) = if index == self.selected {
+                (format!("> {} <", label), Color::RGB(255, 220, 100))
+            } else {
+                (label.clone(), Color::RGB(255, 255, 255))
+            };
+            let style = TextStyle { color, scale: TEXT_SCALE, align: Align::Center };
+            font.draw(canvas, assets, &text, center_x, top + index as i32 * line_height, &style);
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod dialogue;
+pub mod font;
+pub mod inventory_screen;
+pub mod menu;
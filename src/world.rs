@@ -0,0 +1,292 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    entities::{
+        entity::{Entity, EntityId},
+        npc::{self, Npc, NPCS_PATH},
+        player::Player
+    },
+    events::{
+        collision_handler::{CollisionDetector, HitBox},
+        key_handler::{Action, Direction, Keys, PressTracker},
+        warp_handler::{WarpHandler, WARPS_PATH}
+    },
+    items::{
+        item::{ItemLibrary, ITEMS_PATH},
+        pickup::{self, Pickup, PICKUPS_PATH}
+    },
+    tiles::tile_handler::{Map, TileHandler},
+    ui::dialogue::{DialogueLibrary, DIALOGUE_DIR}
+};
+
+/// How far in front of the player's hitbox an NPC can be talked to, in world pixels.
+const TALK_REACH: i32 = 16;
+
+/// Story flag that ends the game once raised.
+pub const GAME_OVER_FLAG: &str = "game_over";
+
+/// Something that happened during a `World::step` which whoever drives the
+/// world has to act on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StepEvent {
+    Warped,                     // The player warped, so views following them should jump
+    Talk(String)                // The player talked to an NPC; holds its dialogue id
+}
+
+/// The game state: maps, tile properties, warps, NPCs, items, the player
+/// and the story flags raised so far.
+///
+/// Nothing in here touches SDL rendering, so a `World` can be created and
+/// stepped without a window, e.g. from `cargo test`:
+///
+/// ```no_run
+/// # use _2d_adventure::{entities::player::Player, events::key_handler::{Action, Keys, PressTracker}, world::World};
+/// let mut world = World::new(48, "002_worldmap", Player::new(460, 364, 3, 48))?;
+/// let right = Keys::holding(&[Action::MoveRight]);
+/// let mut presses = PressTracker::new();
+/// for _ in 0..120 {
+///     presses.update(&right);
+///     world.step(&right, &presses);
+/// }
+/// println!("Stopped at x = {}", world.player.rect.x);
+/// # Ok::<(), String>(())
+/// ```
+pub struct World {
+    pub tile_handler: TileHandler,
+    pub warp_handler: WarpHandler,
+    pub active_map: usize,          // Index into `tile_handler.maps`
+    pub player: Player,
+    pub npcs: Vec<Npc>,             // On every map; only those on the active map move
+    pub dialogues: DialogueLibrary,
+    pub items: ItemLibrary,
+    pub pickups: Vec<Pickup>,       // On every map, including those already collected
+    pub flags: BTreeSet<String>,    // Story flags raised so far, e.g. for collected pickups
+    pub tile_size: u32
+}
+
+impl World {
+    /// Loads tiles, maps, warps, NPCs, dialogue and items and places `player` on `start_map`.
+    ///
+    /// # Arguments
+    /// * `tile_size` - Size of a tile in world pixels
+    /// * `start_map` - Name of the map the player starts on
+    /// * `player` - The player, already at its starting position
+    pub fn new(tile_size: u32, start_map: &str, player: Player) -> Result<Self, String> {
+        let tile_handler = TileHandler::new(tile_size)?;
+        let warp_handler = WarpHandler::load(WARPS_PATH, &tile_handler)
+            .map_err(|e| e.to_string())?;
+        let dialogues = DialogueLibrary::load_dir(DIALOGUE_DIR)
+            .map_err(|e| e.to_string())?;
+        let items = ItemLibrary::load(ITEMS_PATH)
+            .map_err(|e| e.to_string())?;
+
+        let mut world = Self {
+            tile_handler,
+            warp_handler,
+            active_map: 0,
+            player,
+            npcs: Vec::new(),
+            dialogues,
+            items,
+            pickups: Vec::new(),
+            flags: BTreeSet::new(),
+            tile_size
+        };
+        world.populate(start_map)?;
+        Ok(world)
+    }
+
+    /// Starts a new game on the loaded maps: `player` is placed on
+    /// `start_map`, NPCs and pickups are reloaded and the story flags cleared.
+    ///
+    /// # Arguments
+    /// * `start_map` - Name of the map the player starts on
+    /// * `player` - The player, already at its starting position
+    pub fn restart(&mut self, start_map: &str, player: Player) -> Result<(), String> {
+        self.populate(start_map)?;
+        self.player = player;
+        Ok(())
+    }
+
+    /// Loads NPCs and pickups as they are at the start of the game and
+    /// makes `start_map` the active map.
+    fn populate(&mut self, start_map: &str) -> Result<(), String> {
+        let active_map = self.tile_handler.map_index(start_map)
+            .ok_or(format!("Start map '{}' was not found in res/maps", start_map))?;
+        let npcs = npc::load_npcs(NPCS_PATH, &self.tile_handler)
+            .map_err(|e| e.to_string())?;
+        for npc in &npcs {
+            if let Some(id) = npc.dialogue.as_ref().filter(|id| self.dialogues.get(id).is_none()) {
+                return Err(format!("NPC '{}' uses dialogue '{}', which is not in {}", npc.name, id, DIALOGUE_DIR));
+            }
+        }
+        let pickups = pickup::load_pickups(PICKUPS_PATH, &self.tile_handler, &self.items)
+            .map_err(|e| e.to_string())?;
+
+        self.active_map = active_map;
+        self.npcs = npcs;
+        self.pickups = pickups;
+        self.flags.clear();
+        self.warp_handler.reset();
+        Ok(())
+    }
+
+    /// The map the player is on.
+    pub fn map(&self) -> &Map {
+        &self.tile_handler.maps[self.active_map]
+    }
+
+    /// Size of the active map in world pixels.
+    pub fn map_size(&self) -> (u32, u32) {
+        let map = self.map();
+        (map.col_len() * self.tile_size, map.row_len() * self.tile_size)
+    }
+
+    /// Advances the game by one fixed tick, updating every entity on the
+    /// active map. Walking over a pickup puts it in the player's inventory.
+    ///
+    /// Pressing interact next to an NPC with something to say stops the
+    /// tick there and reports it; showing the dialogue, and freezing the
+    /// world meanwhile, is up to the caller.
+    ///
+    /// # Arguments
+    /// * `input` - Actions held during this tick
+    /// * `presses` - Actions pressed this tick, already updated with `input`
+    ///
+    /// # Returns
+    /// What the caller has to act on, if anything
+    pub fn step(&mut self, input: &Keys, presses: &PressTracker) -> Option<StepEvent> {
+        self.player.previous_rect = self.player.rect;
+        for npc in &mut self.npcs {
+            npc.previous_rect = npc.rect;
+        }
+
+        // ===== TALKING =====
+        if presses.was_pressed(Action::Interact) && !self.warp_handler.is_transitioning()
+            && let Some(dialogue) = self.talk()
+        {
+            return Some(StepEvent::Talk(dialogue));
+        }
+
+        // ===== ENTITIES =====
+        // Movement is frozen while a warp fade is playing. Each entity is
+        // blocked by all the others, as they stand before it moves
+        if !self.warp_handler.is_transitioning() {
+            for id in self.entity_ids() {
+                let obstacles = self.hit_boxes_except(id);
+                let collision_handler = CollisionDetector::new(&self.tile_handler, self.tile_size)
+                    .with_obstacles(&obstacles);
+                let map = &self.tile_handler.maps[self.active_map];
+                match id {
+                    EntityId::Player => self.player.update(input, map, &collision_handler),
+                    EntityId::Npc(index) => self.npcs[index].update(map, &collision_handler, self.tile_size)
+                }
+            }
+        }
+
+        // ===== PICKUPS =====
+        self.collect_pickups();
+
+        // ===== WARPS =====
+        // Switch maps when the player steps onto a warp tile
+        let (tile_x, tile_y) = self.player.tile_position(self.tile_size);
+        let warp = self.warp_handler.update(self.active_map, tile_x, tile_y)?;
+        self.active_map = warp.to_map;
+        self.player.move_to_tile(warp.to_x, warp.to_y, self.tile_size);
+        self.player.direction = warp.facing;
+        Some(StepEvent::Warped)
+    }
+
+    /// Stops every character where it stands, for while the world is
+    /// frozen: they play their idle animation and are drawn without
+    /// interpolating from where they were a tick earlier.
+    pub fn stand_still(&mut self) {
+        self.player.velocity_x = 0;
+        self.player.velocity_y = 0;
+        self.player.previous_rect = self.player.rect;
+        for npc in &mut self.npcs {
+            npc.velocity_x = 0;
+            npc.velocity_y = 0;
+            npc.previous_rect = npc.rect;
+        }
+    }
+
+    /// Moves every pickup on the active map that the player's hitbox
+    /// overlaps into the inventory. What does not fit stays on the ground.
+    fn collect_pickups(&mut self) {
+        let hit_box = self.player.hit_box();
+        let touched = self.pickups.iter_mut().filter(|pickup| {
+            pickup.map == self.active_map && !pickup.collected && pickup.hit_box().intersects(&hit_box)
+        });
+        for pickup in touched {
+            pickup.count = self.player.inventory.add(&self.items, &pickup.item, pickup.count);
+            if pickup.count == 0 {
+                pickup.collected = true;
+                self.flags.insert(pickup.flag.clone());
+            }
+        }
+    }
+
+    /// Raises the story flag `flag`. Flags stay raised and are saved, apart
+    /// from `GAME_OVER_FLAG`, which gameplay lowers as it ends the game.
+    pub fn set_flag(&mut self, flag: &str) {
+        self.flags.insert(flag.to_string());
+    }
+
+    /// Turns the NPC right in front of the player to face them, if it has
+    /// something to say.
+    ///
+    /// # Returns
+    /// The id of the NPC's dialogue
+    fn talk(&mut self) -> Option<String> {
+        let mut reach = self.player.hit_box();
+        match self.player.direction {
+            Direction::Up => reach.y -= TALK_REACH,
+            Direction::Down => reach.y += TALK_REACH,
+            Direction::Left => reach.x -= TALK_REACH,
+            Direction::Right => reach.x += TALK_REACH
+        }
+
+        let npc = self.npcs.iter_mut().find(|npc| {
+            npc.map == self.active_map && npc.dialogue.is_some() && npc.hit_box().intersects(&reach)
+        })?;
+        npc.direction = self.player.direction.opposite();
+        npc.dialogue.clone()
+    }
+
+    /// Every entity on the active map, in update order: the player first,
+    /// then NPCs in the order they were loaded.
+    pub fn entity_ids(&self) -> Vec<EntityId> {
+        let npcs = self.npcs.iter()
+            .enumerate()
+            .filter(|(_, npc)| npc.map == self.active_map)
+            .map(|(index, _)| EntityId::Npc(index));
+        std::iter::once(EntityId::Player).chain(npcs).collect()
+    }
+
+    /// The entity called `id`.
+    pub fn entity(&self, id: EntityId) -> &dyn Entity {
+        match id {
+            EntityId::Player => &self.player,
+            EntityId::Npc(index) => &self.npcs[index]
+        }
+    }
+
+    /// Entities on the active map sorted back to front by their foot Y at
+    /// `alpha`, so characters standing behind others are drawn first. Ties
+    /// keep update order.
+    pub fn draw_order(&self, alpha: f32) -> Vec<EntityId> {
+        let mut ids = self.entity_ids();
+        ids.sort_by_key(|id| self.entity(*id).foot_y(alpha));
+        ids
+    }
+
+    /// Hitboxes of every entity on the active map other than `id`.
+    fn hit_boxes_except(&self, id: EntityId) -> Vec<HitBox> {
+        self.entity_ids()
+            .into_iter()
+            .filter(|other| *other != id)
+            .map(|other| self.entity(other).hit_box())
+            .collect()
+    }
+}
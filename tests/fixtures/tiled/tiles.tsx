@@ -0,0 +1,17 @@
+<?xml version="1.0" encoding="UTF-8"?>
+<tileset version="1.10" tiledversion="1.10.2" name="tiles" tilewidth="16" tileheight="16" tilecount="3" columns="0">
+ <grid orientation="orthogonal" width="1" height="1"/>
+ <tile id="0">
+  <image source="../../../res/tiles/001_grass.bmp" width="16" height="16"/>
+ </tile>
+ <tile id="1">
+  <image source="../../../res/tiles/002_wall.bmp" width="16" height="16"/>
+ </tile>
+ <tile id="2">
+  <properties>
+   <property name="tile" value="water"/>
+   <property name="solid" type="bool" value="false"/>
+  </properties>
+  <image source="water_variant.bmp" width="16" height="16"/>
+ </tile>
+</tileset>
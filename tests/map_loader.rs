@@ -0,0 +1,110 @@
+use std::path::Path;
+
+use _2d_adventure::tiles::{
+    map_loader::MapLoadError,
+    tile_handler::{LayerKind, Map}
+};
+
+const TILE_SIZE: u32 = 48;
+const TILE_COUNT: u32 = 6;
+
+fn parse(source: &str) -> Result<Map, MapLoadError> {
+    Map::from_reader(source.as_bytes(), Path::new("test.txt"), TILE_SIZE, TILE_COUNT)
+}
+
+#[test]
+fn a_plain_grid_is_one_ground_layer() {
+    let map = parse("0 1\n2 3\n").expect("grid should parse");
+
+    assert_eq!(map.name, "test");
+    assert_eq!(map.layers.len(), 1);
+    assert_eq!(map.layers[0].kind, LayerKind::Ground);
+    assert!(map.layers[0].collision);
+
+    let pixel = map.layers[0].map[1][0].as_ref().unwrap();
+    assert_eq!((pixel.x, pixel.y, pixel.index), (0, TILE_SIZE as i32, 2));
+}
+
+#[test]
+fn layer_headers_set_kind_and_collision() {
+    let source = "\
+0 0
+0 0
+
+[decoration nocollision]
+4 .
+. 4
+
+[overlay collision]
+. 5
+. .
+";
+    let map = parse(source).expect("layered map should parse");
+
+    let layers: Vec<_> = map.layers.iter()
+        .map(|layer| (layer.name.as_str(), layer.kind, layer.collision))
+        .collect();
+    assert_eq!(layers, [
+        ("ground", LayerKind::Ground, true),
+        ("decoration", LayerKind::Decoration, false),
+        ("overlay", LayerKind::Overlay, true)
+    ]);
+    assert!(map.layers[1].map[0][1].is_none());
+    assert_eq!(map.layers[2].map[0][1].as_ref().map(|pixel| pixel.index), Some(5));
+}
+
+#[test]
+fn layers_without_flags_use_their_kind_defaults() {
+    let map = parse("0\n[decoration]\n4\n[overlay]\n4\n").expect("layered map should parse");
+
+    assert!(map.layers[1].collision);
+    assert!(!map.layers[2].collision);
+}
+
+#[test]
+fn invalid_token_reports_its_line_and_column() {
+    match parse("0 0 0\n0  x 0\n") {
+        Err(MapLoadError::InvalidToken { line, column, token, .. }) => {
+            assert_eq!((line, column, token.as_str()), (2, 4, "x"));
+        },
+        other => panic!("expected an invalid token error, got {:?}", other.err())
+    }
+}
+
+#[test]
+fn unknown_tile_reports_its_line_and_column() {
+    match parse("0 0\n\n[decoration]\n. 6\n. .\n") {
+        Err(MapLoadError::UnknownTile { line, column, index, tile_count, .. }) => {
+            assert_eq!((line, column, index, tile_count), (4, 3, 6, TILE_COUNT));
+        },
+        other => panic!("expected an unknown tile error, got {:?}", other.err())
+    }
+}
+
+#[test]
+fn ragged_row_reports_its_line() {
+    match parse("0 0 0\n0 0 0\n0 0\n") {
+        Err(MapLoadError::RaggedRow { line, expected, found, .. }) => {
+            assert_eq!((line, expected, found), (3, 3, 2));
+        },
+        other => panic!("expected a ragged row error, got {:?}", other.err())
+    }
+}
+
+#[test]
+fn bad_layer_headers_are_rejected() {
+    for source in ["0\n[roof]\n0\n", "0\n[decoration solid]\n0\n", "0\n[decoration\n0\n", "0\n[overlay]\n0\n0\n"] {
+        assert!(matches!(parse(source), Err(MapLoadError::Format { .. })), "{:?}", source);
+    }
+}
+
+#[test]
+fn shipped_house_map_puts_its_trees_on_a_decoration_layer() {
+    let map = Map::load("res/maps/001_map.txt", TILE_SIZE, TILE_COUNT).expect("001_map should load");
+
+    assert_eq!(map.layers.len(), 2);
+    assert_eq!(map.layers[1].kind, LayerKind::Decoration);
+    assert!(map.layers[1].collision);
+    assert_eq!(map.layers[0].map[0][0].as_ref().map(|pixel| pixel.index), Some(0));
+    assert_eq!(map.layers[1].map[0][0].as_ref().map(|pixel| pixel.index), Some(4));
+}
@@ -0,0 +1,109 @@
+use std::path::{Path, PathBuf};
+
+use _2d_adventure::tiles::{
+    map_loader::MapLoadError,
+    tile_handler::{LayerKind, Map},
+    tile_manifest::TileDefinition
+};
+
+const FIXTURES: &str = "tests/fixtures/tiled";
+const TILE_SIZE: u32 = 48;
+
+// Every fixture map is 3x2 and draws the same ground layer
+const GROUND: [[Option<u32>; 3]; 2] = [
+    [Some(0), Some(1), Some(2)],
+    [Some(1), None, Some(0)]
+];
+
+/// Grass, a solid wall and solid water, like the start of the real manifest.
+fn definitions() -> Vec<TileDefinition> {
+    [("grass", "001_grass.bmp", false), ("wall", "002_wall.bmp", true), ("water", "003_water.bmp", true)]
+        .into_iter()
+        .enumerate()
+        .map(|(id, (name, image, solid))| TileDefinition {
+            id: id as u32,
+            name: name.to_string(),
+            image: PathBuf::from(image),
+            solid
+        })
+        .collect()
+}
+
+fn load(file: &str) -> Result<Map, MapLoadError> {
+    Map::load_tiled(Path::new(FIXTURES).join(file), TILE_SIZE, &definitions())
+}
+
+/// The manifest index of every cell in layer `layer`.
+fn indices(map: &Map, layer: usize) -> Vec<Vec<Option<u32>>> {
+    map.layers[layer].map.iter()
+        .map(|row| row.iter().map(|cell| cell.as_ref().map(|pixel| pixel.index)).collect())
+        .collect()
+}
+
+#[test]
+fn csv_layers_resolve_tiles_by_image_and_property() {
+    let map = load("csv.tmj").expect("csv.tmj should load");
+
+    assert_eq!(map.name, "csv");
+    assert_eq!(map.layers.len(), 2);
+    assert_eq!(indices(&map, 0), GROUND);
+
+    let ground = &map.layers[0];
+    assert_eq!(ground.kind, LayerKind::Ground);
+    assert!(ground.collision);
+    let pixel = ground.map[1][2].as_ref().unwrap();
+    assert_eq!((pixel.x, pixel.y), (2 * TILE_SIZE as i32, TILE_SIZE as i32));
+
+    let canopy = &map.layers[1];
+    assert_eq!(canopy.name, "canopy");
+    assert_eq!(canopy.kind, LayerKind::Overlay);
+    assert!(!canopy.collision);
+    assert_eq!(indices(&map, 1), [[None, None, None], [None, Some(0), None]]);
+}
+
+#[test]
+fn base64_layers_decode_with_every_compression() {
+    for file in ["base64.tmx", "base64_zlib.tmx", "base64_gzip.tmx"] {
+        let map = load(file).unwrap_or_else(|e| panic!("{} should load: {}", file, e));
+        assert_eq!(indices(&map, 0), GROUND, "{}", file);
+    }
+}
+
+#[test]
+fn external_tsx_tileset_overrides_solidity() {
+    let map = load("base64.tmx").expect("base64.tmx should load");
+    let ground = &map.layers[0];
+
+    // The water tile is marked walkable in the tileset, the wall keeps the manifest's solidity
+    assert_eq!(ground.map[0][2].as_ref().unwrap().solid, Some(false));
+    assert_eq!(ground.map[0][1].as_ref().unwrap().solid, None);
+}
+
+#[test]
+fn image_tileset_tiles_follow_manifest_order() {
+    let map = load("image_tileset.tmj").expect("image_tileset.tmj should load");
+
+    assert_eq!(indices(&map, 0), [[Some(0), Some(1), Some(2)], [Some(2), Some(1), Some(0)]]);
+    assert_eq!(map.layers[0].map[0][1].as_ref().unwrap().solid, Some(false));
+    assert_eq!(map.layers[0].map[0][2].as_ref().unwrap().solid, None);
+}
+
+#[test]
+fn unknown_gid_is_reported_with_its_cell() {
+    match load("unknown_gid.tmj") {
+        Err(MapLoadError::UnknownGid { layer, x, y, gid, .. }) => {
+            assert_eq!((layer.as_str(), x, y, gid), ("ground", 1, 1, 9));
+        },
+        other => panic!("expected an unknown gid error, got {:?}", other.map(|map| map.name))
+    }
+}
+
+#[test]
+fn unsupported_encoding_is_rejected() {
+    match load("unsupported_encoding.tmx") {
+        Err(MapLoadError::Format { message, .. }) => {
+            assert_eq!(message, "unsupported layer encoding 'hex'");
+        },
+        other => panic!("expected a format error, got {:?}", other.map(|map| map.name))
+    }
+}
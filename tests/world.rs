@@ -0,0 +1,123 @@
+use _2d_adventure::{
+    entities::player::Player,
+    events::key_handler::{Action, Keys, PressTracker},
+    world::{StepEvent, World}
+};
+
+// Where a new game starts: inside the house on the world map
+const START_MAP: &str = "002_worldmap";
+const START_X: i32 = 460;
+const START_Y: i32 = 364;
+const TILE_SIZE: u32 = 48;
+const PLAYER_SPEED: i32 = 3;
+
+fn new_world() -> World {
+    let player = Player::new(START_X, START_Y, PLAYER_SPEED, TILE_SIZE);
+    World::new(TILE_SIZE, START_MAP, player).expect("world should load from res/")
+}
+
+/// Steps `world` for `ticks` ticks with only `action` held.
+fn hold(world: &mut World, action: Action, ticks: u32) {
+    let keys = Keys::holding(&[action]);
+    let mut presses = PressTracker::new();
+    for _ in 0..ticks {
+        presses.update(&keys);
+        world.step(&keys, &presses);
+    }
+}
+
+#[test]
+fn walking_right_from_spawn_stops_at_the_house_wall() {
+    let mut world = new_world();
+    hold(&mut world, Action::MoveRight, 120);
+
+    assert_eq!(world.map().name, START_MAP);
+    assert_eq!(world.player.rect.x, 536);
+    assert_eq!(world.player.rect.y, START_Y);
+}
+
+#[test]
+fn standing_still_keeps_the_player_at_spawn() {
+    let mut world = new_world();
+    let keys = Keys::new();
+    let mut presses = PressTracker::new();
+    for _ in 0..60 {
+        presses.update(&keys);
+        world.step(&keys, &presses);
+    }
+
+    assert_eq!((world.player.rect.x, world.player.rect.y), (START_X, START_Y));
+}
+
+/// Creates a world with the player standing on tile `col`, `row` of `map`.
+fn world_at(map: &str, col: u32, row: u32) -> World {
+    let mut world = new_world();
+    let player = Player::new((col * TILE_SIZE) as i32, (row * TILE_SIZE) as i32, PLAYER_SPEED, TILE_SIZE);
+    world.restart(map, player).expect("map should be loaded");
+    world
+}
+
+/// Steps `world` like `hold`, returning the events it reported in order.
+fn hold_events(world: &mut World, action: Action, ticks: u32) -> Vec<StepEvent> {
+    let keys = Keys::holding(&[action]);
+    let mut presses = PressTracker::new();
+    let mut events = Vec::new();
+    for _ in 0..ticks {
+        presses.update(&keys);
+        events.extend(world.step(&keys, &presses));
+    }
+    events
+}
+
+#[test]
+fn walking_through_the_door_warps_to_the_house() {
+    let mut world = world_at(START_MAP, 10, 12);
+    let keys = Keys::holding(&[Action::MoveUp]);
+    let mut presses = PressTracker::new();
+    let warped = (0..120).any(|_| {
+        presses.update(&keys);
+        world.step(&keys, &presses) == Some(StepEvent::Warped)
+    });
+
+    assert!(warped);
+    assert_eq!(world.map().name, "001_map");
+    assert_eq!(world.player.tile_position(TILE_SIZE), (7, 8));
+}
+
+#[test]
+fn walking_over_a_pickup_collects_it() {
+    let mut world = world_at(START_MAP, 21, 8);
+    hold(&mut world, Action::MoveUp, 30);
+
+    assert_eq!(world.player.inventory.count("apple"), 3);
+    let apples = world.pickups.iter().find(|pickup| pickup.item == "apple").unwrap();
+    assert!(apples.collected);
+    assert!(world.flags.contains(&apples.flag));
+}
+
+#[test]
+fn interacting_next_to_an_npc_starts_their_dialogue() {
+    let mut world = world_at("001_map", 8, 8);
+    hold(&mut world, Action::MoveRight, 10);
+    let events = hold_events(&mut world, Action::Interact, 1);
+
+    assert_eq!(events, [StepEvent::Talk("stranger_farewell".to_string())]);
+}
+
+#[test]
+fn worlds_stepped_alike_stay_alike() {
+    let run = || {
+        let mut world = new_world();
+        hold(&mut world, Action::MoveLeft, 600);
+        hold(&mut world, Action::MoveDown, 600);
+        let npcs: Vec<_> = world.npcs.iter().map(|npc| npc.rect).collect();
+        (world.player.rect, npcs)
+    };
+
+    let (player, npcs) = run();
+    assert_eq!((player, npcs.clone()), run());
+
+    // Only meaningful if the wandering NPCs actually moved
+    let spawned: Vec<_> = new_world().npcs.iter().map(|npc| npc.rect).collect();
+    assert_ne!(npcs, spawned);
+}